@@ -55,6 +55,30 @@ impl Guid {
             ],
         }
     }
+
+    /// Creates a GUID from its in-memory representation, as found in UEFI
+    /// tables and variables
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Guid {
+            a: u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            b: u16::from_le_bytes([bytes[4], bytes[5]]),
+            c: u16::from_le_bytes([bytes[6], bytes[7]]),
+            d: [
+                bytes[8], bytes[9], bytes[10], bytes[11], bytes[12], bytes[13], bytes[14],
+                bytes[15],
+            ],
+        }
+    }
+
+    /// Returns the in-memory representation of the GUID
+    pub fn to_bytes(self) -> [u8; 16] {
+        let mut bytes = [0; 16];
+        bytes[0..4].copy_from_slice(&self.a.to_le_bytes());
+        bytes[4..6].copy_from_slice(&self.b.to_le_bytes());
+        bytes[6..8].copy_from_slice(&self.c.to_le_bytes());
+        bytes[8..16].copy_from_slice(&self.d);
+        bytes
+    }
 }
 
 impl fmt::Display for Guid {
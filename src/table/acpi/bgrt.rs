@@ -0,0 +1,64 @@
+use super::{read_u16, read_u32, read_u64, AcpiError, AcpiTable, Sdt, SDT_HEADER_SIZE};
+
+/// The Boot Graphics Resource Table (BGRT)
+///
+/// It describes the logo which the firmware displayed during boot, so that
+/// the OS can keep it on screen.
+#[derive(Debug, Copy, Clone)]
+pub struct Bgrt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Bgrt<'a> {
+    const SIGNATURE: [u8; 4] = *b"BGRT";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        sdt.check::<Self>(SDT_HEADER_SIZE + 20)
+            .map(|sdt| Bgrt { sdt })
+    }
+}
+
+impl<'a> Bgrt<'a> {
+    /// The underlying table
+    pub fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Version of the table, which is always 1
+    pub fn version(&self) -> u16 {
+        read_u16(self.sdt.bytes, 36).unwrap_or_default()
+    }
+
+    /// Whether the image is currently displayed on screen
+    pub fn is_displayed(&self) -> bool {
+        self.sdt.bytes[38] & 1 != 0
+    }
+
+    /// Clockwise rotation of the image from the display's orientation, in
+    /// degrees
+    pub fn orientation_offset(&self) -> u16 {
+        u16::from((self.sdt.bytes[38] >> 1) & 3) * 90
+    }
+
+    /// Format of the image, which is always 0 (bitmap)
+    pub fn image_type(&self) -> u8 {
+        self.sdt.bytes[39]
+    }
+
+    /// Physical address of the image
+    pub fn image_address(&self) -> u64 {
+        read_u64(self.sdt.bytes, 40).unwrap_or_default()
+    }
+
+    /// Offset of the image's upper left corner from the left of the screen,
+    /// in pixels
+    pub fn image_offset_x(&self) -> u32 {
+        read_u32(self.sdt.bytes, 48).unwrap_or_default()
+    }
+
+    /// Offset of the image's upper left corner from the top of the screen,
+    /// in pixels
+    pub fn image_offset_y(&self) -> u32 {
+        read_u32(self.sdt.bytes, 52).unwrap_or_default()
+    }
+}
@@ -0,0 +1,163 @@
+use super::{read_u16, read_u32, read_u64, read_u8, AcpiError, AcpiTable, GenericAddress, Sdt};
+
+/// The Fixed ACPI Description Table (FADT), whose signature is `FACP`
+///
+/// It describes the fixed hardware registers of the platform, and points to
+/// the DSDT and the FACS.
+#[derive(Debug, Copy, Clone)]
+pub struct Fadt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Fadt<'a> {
+    const SIGNATURE: [u8; 4] = *b"FACP";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        // Size of the ACPI 1.0 table
+        sdt.check::<Self>(116).map(|sdt| Fadt { sdt })
+    }
+}
+
+impl<'a> Fadt<'a> {
+    /// The underlying table
+    pub fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    fn u8_at(&self, offset: usize) -> u8 {
+        read_u8(self.sdt.bytes, offset).unwrap_or_default()
+    }
+
+    fn u16_at(&self, offset: usize) -> u16 {
+        read_u16(self.sdt.bytes, offset).unwrap_or_default()
+    }
+
+    fn u32_at(&self, offset: usize) -> u32 {
+        read_u32(self.sdt.bytes, offset).unwrap_or_default()
+    }
+
+    /// Physical address of the Firmware ACPI Control Structure (FACS)
+    ///
+    /// The 64-bit address is preferred when the table provides one.
+    pub fn facs_address(&self) -> u64 {
+        read_u64(self.sdt.bytes, 132)
+            .filter(|&address| address != 0)
+            .unwrap_or_else(|| u64::from(self.u32_at(36)))
+    }
+
+    /// Physical address of the Differentiated System Description Table (DSDT)
+    ///
+    /// The 64-bit address is preferred when the table provides one.
+    pub fn dsdt_address(&self) -> u64 {
+        read_u64(self.sdt.bytes, 140)
+            .filter(|&address| address != 0)
+            .unwrap_or_else(|| u64::from(self.u32_at(40)))
+    }
+
+    /// Power management profile of the platform, such as 1 for desktops or
+    /// 2 for mobile systems
+    pub fn preferred_pm_profile(&self) -> u8 {
+        self.u8_at(45)
+    }
+
+    /// Interrupt used by the System Control Interrupt (SCI)
+    pub fn sci_interrupt(&self) -> u16 {
+        self.u16_at(46)
+    }
+
+    /// I/O port of the System Management Interrupt (SMI) command register
+    pub fn smi_command_port(&self) -> u32 {
+        self.u32_at(48)
+    }
+
+    /// Value to write to the SMI command port to enable ACPI
+    pub fn acpi_enable(&self) -> u8 {
+        self.u8_at(52)
+    }
+
+    /// Value to write to the SMI command port to disable ACPI
+    pub fn acpi_disable(&self) -> u8 {
+        self.u8_at(53)
+    }
+
+    /// I/O port of the PM1a event register block
+    pub fn pm1a_event_block(&self) -> u32 {
+        self.u32_at(56)
+    }
+
+    /// I/O port of the PM1a control register block
+    pub fn pm1a_control_block(&self) -> u32 {
+        self.u32_at(64)
+    }
+
+    /// I/O port of the power management timer register
+    pub fn pm_timer_block(&self) -> u32 {
+        self.u32_at(76)
+    }
+
+    /// Index of the century in the RTC's CMOS memory, or 0 if unsupported
+    pub fn century(&self) -> u8 {
+        self.u8_at(108)
+    }
+
+    /// IA-PC boot architecture flags
+    pub fn iapc_boot_arch(&self) -> u16 {
+        self.u16_at(109)
+    }
+
+    /// Fixed feature flags
+    pub fn flags(&self) -> u32 {
+        self.u32_at(112)
+    }
+
+    /// Register used to reset the system, since ACPI 2.0
+    pub fn reset_register(&self) -> Option<GenericAddress> {
+        GenericAddress::read(self.sdt.bytes, 116)
+    }
+
+    /// Value to write to the reset register, since ACPI 2.0
+    pub fn reset_value(&self) -> Option<u8> {
+        read_u8(self.sdt.bytes, 128)
+    }
+
+    /// ARM boot architecture flags, since ACPI 5.1
+    pub fn arm_boot_arch(&self) -> Option<u16> {
+        read_u16(self.sdt.bytes, 129)
+    }
+
+    /// Minor version of the table, since ACPI 5.1
+    pub fn minor_version(&self) -> Option<u8> {
+        read_u8(self.sdt.bytes, 131)
+    }
+
+    /// Extended address of the PM1a event register block, since ACPI 2.0
+    pub fn x_pm1a_event_block(&self) -> Option<GenericAddress> {
+        GenericAddress::read(self.sdt.bytes, 148)
+    }
+
+    /// Extended address of the PM1a control register block, since ACPI 2.0
+    pub fn x_pm1a_control_block(&self) -> Option<GenericAddress> {
+        GenericAddress::read(self.sdt.bytes, 172)
+    }
+
+    /// Extended address of the power management timer register, since
+    /// ACPI 2.0
+    pub fn x_pm_timer_block(&self) -> Option<GenericAddress> {
+        GenericAddress::read(self.sdt.bytes, 208)
+    }
+
+    /// Sleep control register of hardware-reduced platforms, since ACPI 5.0
+    pub fn sleep_control_register(&self) -> Option<GenericAddress> {
+        GenericAddress::read(self.sdt.bytes, 244)
+    }
+
+    /// Sleep status register of hardware-reduced platforms, since ACPI 5.0
+    pub fn sleep_status_register(&self) -> Option<GenericAddress> {
+        GenericAddress::read(self.sdt.bytes, 256)
+    }
+
+    /// Identifies the hypervisor vendor, since ACPI 6.0
+    pub fn hypervisor_vendor_identity(&self) -> Option<u64> {
+        read_u64(self.sdt.bytes, 268)
+    }
+}
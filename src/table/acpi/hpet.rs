@@ -0,0 +1,49 @@
+use super::{read_u16, read_u32, AcpiError, AcpiTable, GenericAddress, Sdt, SDT_HEADER_SIZE};
+
+/// The High Precision Event Timer table (HPET)
+#[derive(Debug, Copy, Clone)]
+pub struct Hpet<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Hpet<'a> {
+    const SIGNATURE: [u8; 4] = *b"HPET";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        sdt.check::<Self>(SDT_HEADER_SIZE + 20)
+            .map(|sdt| Hpet { sdt })
+    }
+}
+
+impl<'a> Hpet<'a> {
+    /// The underlying table
+    pub fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Hardware ID of the event timer block, which mirrors the timer's
+    /// capabilities register
+    pub fn event_timer_block_id(&self) -> u32 {
+        read_u32(self.sdt.bytes, 36).unwrap_or_default()
+    }
+
+    /// Address of the timer's registers
+    pub fn base_address(&self) -> GenericAddress {
+        GenericAddress::read(self.sdt.bytes, 40).expect("The length of the HPET was checked")
+    }
+
+    /// Sequence number of the timer
+    pub fn hpet_number(&self) -> u8 {
+        self.sdt.bytes[52]
+    }
+
+    /// Minimum clock tick for periodic interrupts, in timer periods
+    pub fn minimum_tick(&self) -> u16 {
+        read_u16(self.sdt.bytes, 53).unwrap_or_default()
+    }
+
+    /// Page protection and OEM attributes
+    pub fn page_protection(&self) -> u8 {
+        self.sdt.bytes[55]
+    }
+}
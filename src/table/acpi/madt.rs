@@ -0,0 +1,323 @@
+use super::{read_u16, read_u32, read_u64, AcpiError, AcpiTable, Sdt, SDT_HEADER_SIZE};
+use bitflags::bitflags;
+
+/// The Multiple APIC Description Table (MADT), whose signature is `APIC`
+///
+/// It lists the interrupt controllers of the platform: the local APIC or
+/// GIC CPU interface of each processor, and the I/O APICs or GIC
+/// distributors.
+#[derive(Debug, Copy, Clone)]
+pub struct Madt<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Madt<'a> {
+    const SIGNATURE: [u8; 4] = *b"APIC";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        sdt.check::<Self>(Self::ENTRIES_OFFSET)
+            .map(|sdt| Madt { sdt })
+    }
+}
+
+impl<'a> Madt<'a> {
+    /// Offset of the first interrupt controller structure
+    const ENTRIES_OFFSET: usize = SDT_HEADER_SIZE + 8;
+
+    /// The underlying table
+    pub fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Physical address of the local APIC of each processor
+    ///
+    /// This may be overridden by a `MadtEntry::LocalApicAddressOverride`.
+    pub fn local_apic_address(&self) -> u32 {
+        read_u32(self.sdt.bytes, SDT_HEADER_SIZE).unwrap_or_default()
+    }
+
+    /// Multiple APIC flags
+    pub fn flags(&self) -> MadtFlags {
+        MadtFlags::from_bits_truncate(
+            read_u32(self.sdt.bytes, SDT_HEADER_SIZE + 4).unwrap_or_default(),
+        )
+    }
+
+    /// Returns an iterator over the interrupt controller structures
+    pub fn entries(&self) -> MadtEntries<'a> {
+        MadtEntries {
+            bytes: &self.sdt.bytes[Self::ENTRIES_OFFSET..],
+        }
+    }
+}
+
+bitflags! {
+    /// Flags of the MADT
+    pub struct MadtFlags: u32 {
+        /// The system also has a PC-AT-compatible dual-8259 setup, which must
+        /// be disabled before enabling the APICs.
+        const PCAT_COMPAT = 1;
+    }
+}
+
+/// An iterator over the interrupt controller structures of the MADT
+///
+/// Iteration stops at the first structure whose length is invalid.
+#[derive(Debug, Clone)]
+pub struct MadtEntries<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for MadtEntries<'a> {
+    type Item = MadtEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let length = usize::from(*self.bytes.get(1)?);
+        if length < 2 || length > self.bytes.len() {
+            self.bytes = &[];
+            return None;
+        }
+        let (entry, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Some(MadtEntry::parse(entry))
+    }
+}
+
+/// An interrupt controller structure of the MADT
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MadtEntry<'a> {
+    /// The local APIC of a processor.
+    LocalApic(LocalApic),
+    /// An I/O APIC.
+    IoApic(IoApic),
+    /// A mapping from an ISA interrupt to a global system interrupt.
+    InterruptSourceOverride(InterruptSourceOverride),
+    /// The local APIC interrupt input to which an NMI is connected.
+    LocalApicNmi(LocalApicNmi),
+    /// The 64-bit physical address of the local APICs, which overrides
+    /// `Madt::local_apic_address`.
+    LocalApicAddressOverride(u64),
+    /// The local x2APIC of a processor.
+    LocalX2Apic(LocalX2Apic),
+    /// The GIC CPU interface of a processor.
+    Gicc(Gicc),
+    /// A GIC distributor.
+    Gicd(Gicd),
+    /// A structure of another type, or which is too short for its type.
+    Unknown {
+        /// Type of the structure.
+        entry_type: u8,
+        /// The whole structure, including its type and length.
+        data: &'a [u8],
+    },
+}
+
+impl<'a> MadtEntry<'a> {
+    fn parse(data: &'a [u8]) -> Self {
+        let entry_type = data[0];
+        let entry = match entry_type {
+            0 => LocalApic::parse(data).map(MadtEntry::LocalApic),
+            1 => IoApic::parse(data).map(MadtEntry::IoApic),
+            2 => InterruptSourceOverride::parse(data).map(MadtEntry::InterruptSourceOverride),
+            4 => LocalApicNmi::parse(data).map(MadtEntry::LocalApicNmi),
+            5 => read_u64(data, 4).map(MadtEntry::LocalApicAddressOverride),
+            9 => LocalX2Apic::parse(data).map(MadtEntry::LocalX2Apic),
+            11 => Gicc::parse(data).map(MadtEntry::Gicc),
+            12 => Gicd::parse(data).map(MadtEntry::Gicd),
+            _ => None,
+        };
+        entry.unwrap_or(MadtEntry::Unknown { entry_type, data })
+    }
+}
+
+bitflags! {
+    /// Flags of a local APIC or x2APIC
+    pub struct LocalApicFlags: u32 {
+        /// The processor is ready to use.
+        const ENABLED = 1;
+        /// The processor is disabled, but can be enabled at runtime. Only
+        /// meaningful if `ENABLED` is not set.
+        const ONLINE_CAPABLE = 2;
+    }
+}
+
+/// The local APIC of a processor
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LocalApic {
+    /// The ACPI processor UID of the processor.
+    pub processor_uid: u8,
+    /// The ID of the processor's local APIC.
+    pub apic_id: u8,
+    /// Whether the processor can be used.
+    pub flags: LocalApicFlags,
+}
+
+impl LocalApic {
+    fn parse(data: &[u8]) -> Option<Self> {
+        Some(LocalApic {
+            processor_uid: *data.get(2)?,
+            apic_id: *data.get(3)?,
+            flags: LocalApicFlags::from_bits_truncate(read_u32(data, 4)?),
+        })
+    }
+}
+
+/// An I/O APIC
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct IoApic {
+    /// The ID of the I/O APIC.
+    pub id: u8,
+    /// The physical address of the I/O APIC's registers.
+    pub address: u32,
+    /// The global system interrupt of the first input of the I/O APIC.
+    pub gsi_base: u32,
+}
+
+impl IoApic {
+    fn parse(data: &[u8]) -> Option<Self> {
+        Some(IoApic {
+            id: *data.get(2)?,
+            address: read_u32(data, 4)?,
+            gsi_base: read_u32(data, 8)?,
+        })
+    }
+}
+
+/// A mapping from an ISA interrupt to a global system interrupt
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct InterruptSourceOverride {
+    /// The bus of the interrupt source, which is always 0 (ISA).
+    pub bus: u8,
+    /// The ISA interrupt.
+    pub source: u8,
+    /// The global system interrupt it is mapped to.
+    pub gsi: u32,
+    /// Polarity and trigger mode of the interrupt.
+    pub flags: u16,
+}
+
+impl InterruptSourceOverride {
+    fn parse(data: &[u8]) -> Option<Self> {
+        Some(InterruptSourceOverride {
+            bus: *data.get(2)?,
+            source: *data.get(3)?,
+            gsi: read_u32(data, 4)?,
+            flags: read_u16(data, 8)?,
+        })
+    }
+}
+
+/// The local APIC interrupt input to which an NMI is connected
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LocalApicNmi {
+    /// The ACPI processor UID of the processor, or 0xff for all processors.
+    pub processor_uid: u8,
+    /// Polarity and trigger mode of the interrupt.
+    pub flags: u16,
+    /// The local APIC interrupt input (LINTn).
+    pub lint: u8,
+}
+
+impl LocalApicNmi {
+    fn parse(data: &[u8]) -> Option<Self> {
+        Some(LocalApicNmi {
+            processor_uid: *data.get(2)?,
+            flags: read_u16(data, 3)?,
+            lint: *data.get(5)?,
+        })
+    }
+}
+
+/// The local x2APIC of a processor
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct LocalX2Apic {
+    /// The ID of the processor's local x2APIC.
+    pub x2apic_id: u32,
+    /// Whether the processor can be used.
+    pub flags: LocalApicFlags,
+    /// The ACPI processor UID of the processor.
+    pub processor_uid: u32,
+}
+
+impl LocalX2Apic {
+    fn parse(data: &[u8]) -> Option<Self> {
+        Some(LocalX2Apic {
+            x2apic_id: read_u32(data, 4)?,
+            flags: LocalApicFlags::from_bits_truncate(read_u32(data, 8)?),
+            processor_uid: read_u32(data, 12)?,
+        })
+    }
+}
+
+/// The GIC CPU interface of a processor
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Gicc {
+    /// The GIC CPU interface number of the processor.
+    pub cpu_interface_number: u32,
+    /// The ACPI processor UID of the processor.
+    pub processor_uid: u32,
+    /// Whether the processor can be used, and the trigger modes of its
+    /// interrupts. Bit 0 is set if the processor is enabled.
+    pub flags: u32,
+    /// The version of the ARM parking protocol implemented by the processor.
+    pub parking_protocol_version: u32,
+    /// The global system interrupt of the performance monitoring interrupt.
+    pub performance_interrupt: u32,
+    /// The physical address of the processor's parking protocol mailbox.
+    pub parked_address: u64,
+    /// The physical address of the GIC CPU interface.
+    pub physical_base_address: u64,
+    /// The physical address of the GIC virtual CPU interface.
+    pub gicv_base_address: u64,
+    /// The physical address of the GIC virtual interface control block.
+    pub gich_base_address: u64,
+    /// The global system interrupt of the virtual GIC maintenance interrupt.
+    pub vgic_maintenance_interrupt: u32,
+    /// The physical address of the processor's GIC redistributor.
+    pub gicr_base_address: u64,
+    /// The MPIDR of the processor.
+    pub mpidr: u64,
+}
+
+impl Gicc {
+    fn parse(data: &[u8]) -> Option<Self> {
+        Some(Gicc {
+            cpu_interface_number: read_u32(data, 4)?,
+            processor_uid: read_u32(data, 8)?,
+            flags: read_u32(data, 12)?,
+            parking_protocol_version: read_u32(data, 16)?,
+            performance_interrupt: read_u32(data, 20)?,
+            parked_address: read_u64(data, 24)?,
+            physical_base_address: read_u64(data, 32)?,
+            gicv_base_address: read_u64(data, 40)?,
+            gich_base_address: read_u64(data, 48)?,
+            vgic_maintenance_interrupt: read_u32(data, 56)?,
+            gicr_base_address: read_u64(data, 60)?,
+            mpidr: read_u64(data, 68)?,
+        })
+    }
+}
+
+/// A GIC distributor
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Gicd {
+    /// The ID of the distributor.
+    pub gic_id: u32,
+    /// The physical address of the distributor.
+    pub physical_base_address: u64,
+    /// The global system interrupt of the first input of the distributor.
+    pub gsi_base: u32,
+    /// The version of the GIC architecture, or 0 if it is not specified.
+    pub gic_version: u8,
+}
+
+impl Gicd {
+    fn parse(data: &[u8]) -> Option<Self> {
+        Some(Gicd {
+            gic_id: read_u32(data, 4)?,
+            physical_base_address: read_u64(data, 8)?,
+            gsi_base: read_u32(data, 16)?,
+            gic_version: *data.get(20)?,
+        })
+    }
+}
@@ -0,0 +1,95 @@
+use super::{read_u16, read_u64, AcpiError, AcpiTable, Sdt, SDT_HEADER_SIZE};
+
+/// The PCI Express memory mapped configuration table (MCFG)
+///
+/// It gives the base address of the Enhanced Configuration Access Mechanism
+/// (ECAM) region of each PCI segment group.
+#[derive(Debug, Copy, Clone)]
+pub struct Mcfg<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Mcfg<'a> {
+    const SIGNATURE: [u8; 4] = *b"MCFG";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        sdt.check::<Self>(Self::ENTRIES_OFFSET)
+            .map(|sdt| Mcfg { sdt })
+    }
+}
+
+impl<'a> Mcfg<'a> {
+    /// Offset of the first allocation structure
+    const ENTRIES_OFFSET: usize = SDT_HEADER_SIZE + 8;
+
+    /// Size of an allocation structure
+    const ENTRY_SIZE: usize = 16;
+
+    /// The underlying table
+    pub fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Returns an iterator over the ECAM regions
+    pub fn entries(&self) -> McfgEntries<'a> {
+        McfgEntries {
+            entries: self.sdt.bytes[Self::ENTRIES_OFFSET..].chunks_exact(Self::ENTRY_SIZE),
+        }
+    }
+}
+
+/// The ECAM region of a PCI segment group
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct McfgEntry {
+    /// The physical address of the region, which corresponds to bus 0 even
+    /// if the region starts at another bus.
+    pub base_address: u64,
+    /// The PCI segment group.
+    pub segment_group: u16,
+    /// The first PCI bus which is decoded by the region.
+    pub start_bus: u8,
+    /// The last PCI bus which is decoded by the region.
+    pub end_bus: u8,
+}
+
+impl McfgEntry {
+    /// Physical address of the configuration space of a PCI function
+    ///
+    /// Returns `None` if the bus is not decoded by this region, if the
+    /// device or function number is out of range, or if the address does
+    /// not fit in 64 bits.
+    pub fn function_address(&self, bus: u8, device: u8, function: u8) -> Option<u64> {
+        if bus < self.start_bus || bus > self.end_bus || device >= 32 || function >= 8 {
+            return None;
+        }
+        let offset =
+            (u64::from(bus) << 20) | (u64::from(device) << 15) | (u64::from(function) << 12);
+        self.base_address.checked_add(offset)
+    }
+}
+
+/// An iterator over the ECAM regions listed in the MCFG
+#[derive(Debug, Clone)]
+pub struct McfgEntries<'a> {
+    entries: core::slice::ChunksExact<'a, u8>,
+}
+
+impl Iterator for McfgEntries<'_> {
+    type Item = McfgEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.entries.next()?;
+        Some(McfgEntry {
+            base_address: read_u64(entry, 0)?,
+            segment_group: read_u16(entry, 8)?,
+            start_bus: entry[10],
+            end_bus: entry[11],
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl ExactSizeIterator for McfgEntries<'_> {}
@@ -0,0 +1,459 @@
+//! ACPI table discovery and parsing.
+//!
+//! The firmware publishes the Root System Description Pointer (RSDP) in the
+//! configuration table, under `cfg::ACPI2_GUID` or `cfg::ACPI_GUID`. The RSDP
+//! points to the root table (XSDT, or RSDT before ACPI 2.0), which lists the
+//! addresses of all the other tables.
+//!
+//! Every table is checked against its checksum before being returned, and is
+//! then accessed as a view over its bytes: fields which are missing from
+//! tables of an older revision are reported as `None`.
+
+use super::cfg::{ConfigTableEntry, ACPI2_GUID, ACPI_GUID};
+use core::convert::TryInto;
+use core::ffi::c_void;
+use core::slice;
+
+mod bgrt;
+pub use self::bgrt::Bgrt;
+
+mod fadt;
+pub use self::fadt::Fadt;
+
+mod hpet;
+pub use self::hpet::Hpet;
+
+mod madt;
+pub use self::madt::{
+    Gicc, Gicd, InterruptSourceOverride, IoApic, LocalApic, LocalApicFlags, LocalApicNmi,
+    LocalX2Apic, Madt, MadtEntries, MadtEntry, MadtFlags,
+};
+
+mod mcfg;
+pub use self::mcfg::{Mcfg, McfgEntries, McfgEntry};
+
+mod spcr;
+pub use self::spcr::Spcr;
+
+/// Errors that can be detected when validating ACPI tables
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum AcpiError {
+    /// No RSDP was found in the configuration table.
+    RsdpNotFound,
+    /// The RSDP does not start with `"RSD PTR "`.
+    InvalidRsdpSignature,
+    /// The RSDP is too small for its revision.
+    InvalidRsdpLength,
+    /// The checksum of the RSDP is wrong.
+    InvalidRsdpChecksum,
+    /// A table does not have the expected signature.
+    InvalidSignature([u8; 4]),
+    /// A table is too small for its header or its fixed fields.
+    InvalidLength([u8; 4]),
+    /// The checksum of a table is wrong.
+    InvalidChecksum([u8; 4]),
+    /// The address of a table is null, for example in an entry of the XSDT
+    /// or RSDT.
+    NullAddress,
+}
+
+/// Size of the header shared by all system description tables
+const SDT_HEADER_SIZE: usize = 36;
+
+/// The Root System Description Pointer
+#[derive(Debug, Copy, Clone)]
+pub struct Rsdp<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Rsdp<'a> {
+    /// Size of the ACPI 1.0 structure
+    const V1_SIZE: usize = 20;
+    /// Size of the ACPI 2.0 structure
+    const V2_SIZE: usize = 36;
+
+    /// Checks the RSDP found at `address`
+    ///
+    /// # Safety
+    ///
+    /// `address` must be null or point to readable memory holding an RSDP,
+    /// which lives for `'a`.
+    pub unsafe fn from_address(address: *const c_void) -> Result<Self, AcpiError> {
+        if address.is_null() {
+            return Err(AcpiError::NullAddress);
+        }
+        let bytes = slice::from_raw_parts(address as *const u8, Self::V1_SIZE);
+        if &bytes[..8] != b"RSD PTR " {
+            return Err(AcpiError::InvalidRsdpSignature);
+        }
+        if !checksum_is_valid(bytes) {
+            return Err(AcpiError::InvalidRsdpChecksum);
+        }
+        if bytes[15] < 2 {
+            return Ok(Rsdp { bytes });
+        }
+
+        // ACPI 2.0 extends the structure, and checks all of it
+        let length = read_u32(
+            slice::from_raw_parts(address as *const u8, Self::V2_SIZE),
+            20,
+        )
+        .unwrap_or_default() as usize;
+        if length < Self::V2_SIZE {
+            return Err(AcpiError::InvalidRsdpLength);
+        }
+        let bytes = slice::from_raw_parts(address as *const u8, length);
+        if checksum_is_valid(bytes) {
+            Ok(Rsdp { bytes })
+        } else {
+            Err(AcpiError::InvalidRsdpChecksum)
+        }
+    }
+
+    /// Identifies the OEM
+    pub fn oem_id(&self) -> [u8; 6] {
+        read_array(self.bytes, 9).unwrap_or_default()
+    }
+
+    /// Revision of the structure: 0 for ACPI 1.0, 2 for ACPI 2.0 and later
+    pub fn revision(&self) -> u8 {
+        self.bytes[15]
+    }
+
+    /// Physical address of the RSDT
+    pub fn rsdt_address(&self) -> u32 {
+        read_u32(self.bytes, 16).unwrap_or_default()
+    }
+
+    /// Physical address of the XSDT, since ACPI 2.0
+    pub fn xsdt_address(&self) -> Option<u64> {
+        read_u64(self.bytes, 24).filter(|&address| address != 0)
+    }
+}
+
+/// A system description table, whose length and checksum have been checked
+#[derive(Debug, Copy, Clone)]
+pub struct Sdt<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Sdt<'a> {
+    /// Checks the table found at `address`
+    ///
+    /// # Safety
+    ///
+    /// `address` must be null or point to readable memory holding a system
+    /// description table, which lives for `'a`.
+    pub unsafe fn from_address(address: *const c_void) -> Result<Self, AcpiError> {
+        if address.is_null() {
+            return Err(AcpiError::NullAddress);
+        }
+        let header = slice::from_raw_parts(address as *const u8, SDT_HEADER_SIZE);
+        let signature = read_array(header, 0).unwrap_or_default();
+        let length = read_u32(header, 4).unwrap_or_default() as usize;
+        if length < SDT_HEADER_SIZE {
+            return Err(AcpiError::InvalidLength(signature));
+        }
+        let bytes = slice::from_raw_parts(address as *const u8, length);
+        if checksum_is_valid(bytes) {
+            Ok(Sdt { bytes })
+        } else {
+            Err(AcpiError::InvalidChecksum(signature))
+        }
+    }
+
+    /// Signature of the table, such as `*b"APIC"`
+    pub fn signature(&self) -> [u8; 4] {
+        read_array(self.bytes, 0).unwrap_or_default()
+    }
+
+    /// Revision of the table's structure
+    pub fn revision(&self) -> u8 {
+        self.bytes[8]
+    }
+
+    /// Identifies the OEM
+    pub fn oem_id(&self) -> [u8; 6] {
+        read_array(self.bytes, 10).unwrap_or_default()
+    }
+
+    /// Identifies the table among those of the OEM
+    pub fn oem_table_id(&self) -> [u8; 8] {
+        read_array(self.bytes, 16).unwrap_or_default()
+    }
+
+    /// Revision of the table, as defined by the OEM
+    pub fn oem_revision(&self) -> u32 {
+        read_u32(self.bytes, 24).unwrap_or_default()
+    }
+
+    /// Identifies the tool which created the table
+    pub fn creator_id(&self) -> u32 {
+        read_u32(self.bytes, 28).unwrap_or_default()
+    }
+
+    /// Revision of the tool which created the table
+    pub fn creator_revision(&self) -> u32 {
+        read_u32(self.bytes, 32).unwrap_or_default()
+    }
+
+    /// The whole table, header included
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Checks the signature and length of a table of type `T`
+    fn check<T: AcpiTable<'a>>(self, min_length: usize) -> Result<Self, AcpiError> {
+        if self.signature() != T::SIGNATURE {
+            Err(AcpiError::InvalidSignature(self.signature()))
+        } else if self.bytes.len() < min_length {
+            Err(AcpiError::InvalidLength(self.signature()))
+        } else {
+            Ok(self)
+        }
+    }
+}
+
+/// Common trait implemented by the typed ACPI tables
+pub trait AcpiTable<'a>: Sized {
+    /// Signature identifying the table
+    const SIGNATURE: [u8; 4];
+
+    /// Checks that a system description table is a valid table of this type
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError>;
+}
+
+/// The ACPI tables published by the firmware
+#[derive(Debug, Copy, Clone)]
+pub struct AcpiTables<'a> {
+    rsdp: Rsdp<'a>,
+    root: Sdt<'a>,
+    entry_size: usize,
+}
+
+impl<'a> AcpiTables<'a> {
+    /// Finds the ACPI tables through the RSDP in the configuration table,
+    /// preferring the ACPI 2.0 entry
+    ///
+    /// # Safety
+    ///
+    /// The ACPI tables must be identity-mapped and live for `'a`. This is
+    /// the case until the OS reclaims the memory which holds them.
+    pub unsafe fn from_config_table(entries: &[ConfigTableEntry]) -> Result<Self, AcpiError> {
+        let rsdp = entries
+            .iter()
+            .find(|entry| entry.guid == ACPI2_GUID)
+            .or_else(|| entries.iter().find(|entry| entry.guid == ACPI_GUID))
+            .ok_or(AcpiError::RsdpNotFound)?;
+        Self::from_rsdp(rsdp.address)
+    }
+
+    /// Finds the ACPI tables through the RSDP at `address`
+    ///
+    /// The XSDT is used if there is one, and the RSDT otherwise.
+    ///
+    /// # Safety
+    ///
+    /// `address` must point to an RSDP, and the ACPI tables must be
+    /// identity-mapped and live for `'a`.
+    pub unsafe fn from_rsdp(address: *const c_void) -> Result<Self, AcpiError> {
+        let rsdp = Rsdp::from_address(address)?;
+        let (address, signature, entry_size) = match rsdp.xsdt_address() {
+            Some(xsdt) => (xsdt as usize, *b"XSDT", 8),
+            None => (rsdp.rsdt_address() as usize, *b"RSDT", 4),
+        };
+        let root = Sdt::from_address(address as *const c_void)?;
+        if root.signature() != signature {
+            return Err(AcpiError::InvalidSignature(root.signature()));
+        }
+        Ok(AcpiTables {
+            rsdp,
+            root,
+            entry_size,
+        })
+    }
+
+    /// The RSDP through which the tables were found
+    pub fn rsdp(&self) -> Rsdp<'a> {
+        self.rsdp
+    }
+
+    /// The root table, which is either the XSDT or the RSDT
+    pub fn root(&self) -> Sdt<'a> {
+        self.root
+    }
+
+    /// Returns an iterator over the tables listed by the root table
+    pub fn tables(&self) -> Tables<'a> {
+        Tables {
+            entries: self.root.bytes[SDT_HEADER_SIZE..].chunks_exact(self.entry_size),
+        }
+    }
+
+    /// Finds the first table of type `T`
+    ///
+    /// Returns `None` if there is no such table. Tables with another
+    /// signature, and null entries, are skipped without being checked.
+    pub fn find<T: AcpiTable<'a>>(&self) -> Option<Result<T, AcpiError>> {
+        self.tables()
+            .entries
+            .map(table_address)
+            .filter(|address| !address.is_null())
+            .find(|&address| unsafe { read_signature(address) } == T::SIGNATURE)
+            .map(|address| unsafe { Sdt::from_address(address) }.and_then(T::from_sdt))
+    }
+
+    /// Finds the Fixed ACPI Description Table
+    pub fn fadt(&self) -> Option<Result<Fadt<'a>, AcpiError>> {
+        self.find()
+    }
+
+    /// Finds the Multiple APIC Description Table
+    pub fn madt(&self) -> Option<Result<Madt<'a>, AcpiError>> {
+        self.find()
+    }
+
+    /// Finds the PCI Express memory mapped configuration table
+    pub fn mcfg(&self) -> Option<Result<Mcfg<'a>, AcpiError>> {
+        self.find()
+    }
+
+    /// Finds the High Precision Event Timer table
+    pub fn hpet(&self) -> Option<Result<Hpet<'a>, AcpiError>> {
+        self.find()
+    }
+
+    /// Finds the Serial Port Console Redirection table
+    pub fn spcr(&self) -> Option<Result<Spcr<'a>, AcpiError>> {
+        self.find()
+    }
+
+    /// Finds the Boot Graphics Resource Table
+    pub fn bgrt(&self) -> Option<Result<Bgrt<'a>, AcpiError>> {
+        self.find()
+    }
+}
+
+/// An iterator over the tables listed by the XSDT or RSDT
+#[derive(Debug)]
+pub struct Tables<'a> {
+    entries: slice::ChunksExact<'a, u8>,
+}
+
+impl<'a> Iterator for Tables<'a> {
+    type Item = Result<Sdt<'a>, AcpiError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The root table was checked when `AcpiTables` was created, and its
+        // safety contract covers the tables it lists
+        self.entries
+            .next()
+            .map(|entry| unsafe { Sdt::from_address(table_address(entry)) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl ExactSizeIterator for Tables<'_> {}
+
+/// Decodes an entry of the XSDT or RSDT
+fn table_address(entry: &[u8]) -> *const c_void {
+    let address = match entry.len() {
+        8 => read_u64(entry, 0).unwrap_or_default(),
+        _ => u64::from(read_u32(entry, 0).unwrap_or_default()),
+    };
+    address as usize as *const c_void
+}
+
+/// Reads the signature of the table at `address`, without checking it
+unsafe fn read_signature(address: *const c_void) -> [u8; 4] {
+    read_array(slice::from_raw_parts(address as *const u8, 4), 0).unwrap_or_default()
+}
+
+/// Checks that the bytes of a structure sum to zero
+fn checksum_is_valid(bytes: &[u8]) -> bool {
+    bytes.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)) == 0
+}
+
+/// Generic Address Structure, which describes the location of a register
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct GenericAddress {
+    /// The address space of the register.
+    pub address_space: AddressSpace,
+    /// Size of the register, in bits.
+    pub bit_width: u8,
+    /// Offset of the register at the address, in bits.
+    pub bit_offset: u8,
+    /// Size of the accesses to the register.
+    pub access_size: u8,
+    /// Address of the register in its address space.
+    pub address: u64,
+}
+
+impl GenericAddress {
+    /// Size of the structure
+    const SIZE: usize = 12;
+
+    /// Reads the structure at `offset` in `bytes`
+    fn read(bytes: &[u8], offset: usize) -> Option<Self> {
+        let bytes = bytes.get(offset..offset + Self::SIZE)?;
+        Some(GenericAddress {
+            address_space: AddressSpace(bytes[0]),
+            bit_width: bytes[1],
+            bit_offset: bytes[2],
+            access_size: bytes[3],
+            address: read_u64(bytes, 4)?,
+        })
+    }
+}
+
+newtype_enum! {
+/// Address space of a `GenericAddress`
+pub enum AddressSpace: u8 => {
+    /// System memory
+    SYSTEM_MEMORY = 0x00,
+    /// System I/O ports
+    SYSTEM_IO = 0x01,
+    /// PCI configuration space
+    PCI_CONFIG = 0x02,
+    /// Embedded controller
+    EMBEDDED_CONTROLLER = 0x03,
+    /// System management bus
+    SMBUS = 0x04,
+    /// CMOS memory
+    SYSTEM_CMOS = 0x05,
+    /// PCI BAR target
+    PCI_BAR_TARGET = 0x06,
+    /// Intelligent Platform Management Infrastructure
+    IPMI = 0x07,
+    /// General purpose I/O
+    GENERAL_PURPOSE_IO = 0x08,
+    /// Generic serial bus
+    GENERIC_SERIAL_BUS = 0x09,
+    /// Platform communications channel
+    PLATFORM_COMMUNICATIONS_CHANNEL = 0x0a,
+    /// Functional fixed hardware
+    FUNCTIONAL_FIXED_HARDWARE = 0x7f,
+}}
+
+fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> Option<[u8; N]> {
+    bytes.get(offset..offset + N)?.try_into().ok()
+}
+
+fn read_u8(bytes: &[u8], offset: usize) -> Option<u8> {
+    bytes.get(offset).copied()
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    read_array(bytes, offset).map(u16::from_le_bytes)
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    read_array(bytes, offset).map(u32::from_le_bytes)
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    read_array(bytes, offset).map(u64::from_le_bytes)
+}
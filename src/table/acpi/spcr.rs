@@ -0,0 +1,97 @@
+use super::{read_u16, read_u32, AcpiError, AcpiTable, GenericAddress, Sdt, SDT_HEADER_SIZE};
+
+/// The Serial Port Console Redirection table (SPCR)
+///
+/// It describes the serial port which the firmware uses as a console, so
+/// that the OS can keep using it.
+#[derive(Debug, Copy, Clone)]
+pub struct Spcr<'a> {
+    sdt: Sdt<'a>,
+}
+
+impl<'a> AcpiTable<'a> for Spcr<'a> {
+    const SIGNATURE: [u8; 4] = *b"SPCR";
+
+    fn from_sdt(sdt: Sdt<'a>) -> Result<Self, AcpiError> {
+        sdt.check::<Self>(SDT_HEADER_SIZE + 44)
+            .map(|sdt| Spcr { sdt })
+    }
+}
+
+impl<'a> Spcr<'a> {
+    /// The underlying table
+    pub fn sdt(&self) -> Sdt<'a> {
+        self.sdt
+    }
+
+    /// Type of the serial port interface, such as 0 for a 16550 UART or 3
+    /// for an ARM PL011 UART
+    pub fn interface_type(&self) -> u8 {
+        self.sdt.bytes[36]
+    }
+
+    /// Address of the serial port's registers
+    pub fn base_address(&self) -> GenericAddress {
+        GenericAddress::read(self.sdt.bytes, 40).expect("The length of the SPCR was checked")
+    }
+
+    /// Kinds of interrupts used by the serial port: bit 0 for a PC-AT
+    /// interrupt, bit 1 for an I/O APIC interrupt, bit 3 for a GIC interrupt
+    pub fn interrupt_type(&self) -> u8 {
+        self.sdt.bytes[52]
+    }
+
+    /// PC-AT interrupt of the serial port
+    pub fn irq(&self) -> u8 {
+        self.sdt.bytes[53]
+    }
+
+    /// Global system interrupt of the serial port
+    pub fn gsi(&self) -> u32 {
+        read_u32(self.sdt.bytes, 54).unwrap_or_default()
+    }
+
+    /// Baud rate used by the firmware
+    ///
+    /// Returns `None` if the firmware relies on the current configuration
+    /// of the serial port.
+    pub fn baud_rate(&self) -> Option<u32> {
+        match self.sdt.bytes[58] {
+            3 => Some(9600),
+            4 => Some(19200),
+            6 => Some(57600),
+            7 => Some(115_200),
+            _ => None,
+        }
+    }
+
+    /// Parity, which is always 0 (no parity)
+    pub fn parity(&self) -> u8 {
+        self.sdt.bytes[59]
+    }
+
+    /// Stop bits, which is always 1 (one stop bit)
+    pub fn stop_bits(&self) -> u8 {
+        self.sdt.bytes[60]
+    }
+
+    /// Flow control: bit 0 for DCD, bit 1 for RTS/CTS, bit 2 for XON/XOFF
+    pub fn flow_control(&self) -> u8 {
+        self.sdt.bytes[61]
+    }
+
+    /// Terminal type: 0 for VT100, 1 for VT100+, 2 for VT-UTF8, 3 for ANSI
+    pub fn terminal_type(&self) -> u8 {
+        self.sdt.bytes[62]
+    }
+
+    /// PCI device ID of the serial port, or 0xffff if it is not a PCI device
+    pub fn pci_device_id(&self) -> u16 {
+        read_u16(self.sdt.bytes, 64).unwrap_or_default()
+    }
+
+    /// PCI vendor ID of the serial port, or 0xffff if it is not a PCI device
+    pub fn pci_vendor_id(&self) -> u16 {
+        read_u16(self.sdt.bytes, 66).unwrap_or_default()
+    }
+}
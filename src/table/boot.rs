@@ -1,24 +1,29 @@
 //! UEFI services available during boot.
 
-use super::Header;
+use super::{Header, Revision};
 use crate::proto::{
     loaded_image::{DevicePath, LoadedImage},
     Protocol,
 };
+use crate::result::{Completion, Error};
 use crate::{data_types::Align, proto::media::fs::SimpleFileSystem};
-use crate::{Event, Guid, Handle, Result, Status};
+use crate::{CStr16, Event, Guid, Handle, Identify, Result, Status};
 #[cfg(feature = "exts")]
 use alloc_api::vec::Vec;
 use bitflags::bitflags;
 use core::cell::UnsafeCell;
 use core::ffi::c_void;
+use core::fmt;
 use core::mem::{self, MaybeUninit};
-use core::ptr;
+use core::ops::{Deref, DerefMut};
+use core::ptr::{self, NonNull};
+use core::slice;
+use log::warn;
 
 /// Contains pointers to all of the boot services.
 #[repr(C)]
 pub struct BootServices {
-    header: Header,
+    pub(super) header: Header,
 
     // Task Priority services
     raise_tpl: unsafe extern "efiapi" fn(new_tpl: Tpl) -> Tpl,
@@ -57,18 +62,33 @@ pub struct BootServices {
         events: *mut Event,
         out_index: *mut usize,
     ) -> Status,
-    signal_event: usize,
-    close_event: usize,
-    check_event: usize,
+    signal_event: extern "efiapi" fn(event: Event) -> Status,
+    close_event: unsafe extern "efiapi" fn(event: Event) -> Status,
+    check_event: unsafe extern "efiapi" fn(event: Event) -> Status,
 
     // Protocol handlers
-    install_protocol_interface: usize,
-    reinstall_protocol_interface: usize,
-    uninstall_protocol_interface: usize,
+    install_protocol_interface: extern "efiapi" fn(
+        handle: &mut Handle,
+        guid: &Guid,
+        interface_type: u32,
+        interface: *mut c_void,
+    ) -> Status,
+    reinstall_protocol_interface: extern "efiapi" fn(
+        handle: Handle,
+        protocol: &Guid,
+        old_interface: *mut c_void,
+        new_interface: *mut c_void,
+    ) -> Status,
+    uninstall_protocol_interface:
+        extern "efiapi" fn(handle: Handle, protocol: &Guid, interface: *mut c_void) -> Status,
     handle_protocol:
         extern "efiapi" fn(handle: Handle, proto: &Guid, out_proto: &mut *mut c_void) -> Status,
     _reserved: usize,
-    register_protocol_notify: usize,
+    register_protocol_notify: extern "efiapi" fn(
+        protocol: &Guid,
+        event: Event,
+        registration: *mut ProtocolSearchKey,
+    ) -> Status,
     locate_handle: unsafe extern "efiapi" fn(
         search_ty: i32,
         proto: *const Guid,
@@ -81,14 +101,15 @@ pub struct BootServices {
         device_path: &mut *mut DevicePath,
         out_handle: *mut Handle,
     ) -> Status,
-    install_configuration_table: usize,
+    install_configuration_table:
+        extern "efiapi" fn(guid_entry: &Guid, table_ptr: *const c_void) -> Status,
 
     // Image services
     load_image: extern "efiapi" fn(
         boot_policy: bool,
         parent_image_handle: Handle,
         device_path: *const DevicePath,
-        source_buffer: *mut c_void,
+        source_buffer: *const u8,
         source_size: usize,
         image_handle: &mut Handle
     ) -> Status,
@@ -108,7 +129,7 @@ pub struct BootServices {
         unsafe extern "efiapi" fn(image_handle: Handle, map_key: MemoryMapKey) -> Status,
 
     // Misc services
-    get_next_monotonic_count: usize,
+    get_next_monotonic_count: extern "efiapi" fn(count: &mut u64) -> Status,
     stall: extern "efiapi" fn(microseconds: usize) -> Status,
     set_watchdog_timer: unsafe extern "efiapi" fn(
         timeout: usize,
@@ -131,12 +152,33 @@ pub struct BootServices {
     ) -> Status,
 
     // Protocol open / close services
-    open_protocol: usize,
-    close_protocol: usize,
-    open_protocol_information: usize,
+    open_protocol: extern "efiapi" fn(
+        handle: Handle,
+        protocol: &Guid,
+        interface: &mut *mut c_void,
+        agent_handle: Handle,
+        controller_handle: Handle,
+        attributes: u32,
+    ) -> Status,
+    close_protocol: extern "efiapi" fn(
+        handle: Handle,
+        protocol: &Guid,
+        agent_handle: Handle,
+        controller_handle: Handle,
+    ) -> Status,
+    open_protocol_information: extern "efiapi" fn(
+        handle: Handle,
+        protocol: &Guid,
+        entry_buffer: &mut *mut OpenProtocolInformationEntry,
+        entry_count: &mut usize,
+    ) -> Status,
 
     // Library services
-    protocols_per_handle: usize,
+    protocols_per_handle: extern "efiapi" fn(
+        handle: Handle,
+        protocol_buffer: &mut *mut &Guid,
+        protocol_buffer_count: &mut usize,
+    ) -> Status,
     locate_handle_buffer: unsafe extern "efiapi" fn(
         search_type: i32,
         guid: *const Guid,
@@ -149,18 +191,28 @@ pub struct BootServices {
         registration: *mut c_void,
         out_proto: &mut *mut c_void,
     ) -> Status,
+    // These two functions are C-variadic, which cannot be expressed with the
+    // `efiapi` calling convention. They are emulated in Rust instead.
     install_multiple_protocol_interfaces: usize,
     uninstall_multiple_protocol_interfaces: usize,
 
     // CRC services
-    calculate_crc32: usize,
+    calculate_crc32:
+        extern "efiapi" fn(data: *const c_void, data_size: usize, crc32: &mut u32) -> Status,
 
     // Misc services
     copy_mem: unsafe extern "efiapi" fn(dest: *mut u8, src: *const u8, len: usize),
     set_mem: unsafe extern "efiapi" fn(buffer: *mut u8, len: usize, value: u8),
 
     // New event functions (UEFI 2.0 or newer)
-    create_event_ex: usize,
+    create_event_ex: unsafe extern "efiapi" fn(
+        ty: EventType,
+        notify_tpl: Tpl,
+        notify_fn: Option<EventNotifyFn>,
+        notify_ctx: *mut c_void,
+        event_group: *const Guid,
+        event: *mut Event,
+    ) -> Status,
 }
 
 impl BootServices {
@@ -310,6 +362,9 @@ impl BootServices {
     /// will be delivered next time `wait_for_event` or `check_event` is called.
     /// In both cases, a `notify_fn` callback must be specified.
     ///
+    /// The event is closed when the returned `OwnedEvent` is dropped. Use
+    /// `OwnedEvent::into_raw` to keep it alive for the rest of the program.
+    ///
     /// # Safety
     ///
     /// This function is unsafe because callbacks must handle exit from boot
@@ -319,23 +374,10 @@ impl BootServices {
         event_ty: EventType,
         notify_tpl: Tpl,
         notify_fn: Option<fn(Event)>,
-    ) -> Result<Event> {
+    ) -> Result<OwnedEvent<'_>> {
         // Prepare storage for the output Event
         let mut event = MaybeUninit::<Event>::uninit();
-
-        // Use a trampoline to handle the impedance mismatch between Rust & C
-        unsafe extern "efiapi" fn notify_trampoline(e: Event, ctx: *mut c_void) {
-            let notify_fn: fn(Event) = mem::transmute(ctx);
-            notify_fn(e); // SAFETY: Aborting panics are assumed here
-        }
-        let (notify_func, notify_ctx) = notify_fn
-            .map(|notify_fn| {
-                (
-                    Some(notify_trampoline as EventNotifyFn),
-                    notify_fn as fn(Event) as *mut c_void,
-                )
-            })
-            .unwrap_or((None, ptr::null_mut()));
+        let (notify_func, notify_ctx) = notify_trampoline_args(notify_fn);
 
         // Now we're ready to call UEFI
         (self.create_event)(
@@ -345,7 +387,57 @@ impl BootServices {
             notify_ctx,
             event.as_mut_ptr(),
         )
-        .into_with_val(|| event.assume_init())
+        .into_with_val(|| OwnedEvent {
+            event: event.assume_init(),
+            boot_services: self,
+        })
+    }
+
+    /// Creates an event, optionally placing it in an event group
+    ///
+    /// This works like `create_event`, but the event can be added to a group
+    /// identified by a GUID, such as `EVENT_GROUP_READY_TO_BOOT`. When any
+    /// event of a group is signaled, all the events in that group are
+    /// signaled. Events of the `SIGNAL_EXIT_BOOT_SERVICES` and
+    /// `SIGNAL_VIRTUAL_ADDRESS_CHANGE` types cannot be placed in a group, use
+    /// the `NOTIFY_SIGNAL` type along with the matching group instead.
+    ///
+    /// This function was added in UEFI 2.0, and will return an `Unsupported`
+    /// error on older firmware. `SystemTable::uefi_revision()` can be used to
+    /// check the revision of the firmware beforehand.
+    ///
+    /// # Safety
+    ///
+    /// This function is unsafe because callbacks must handle exit from boot
+    /// services correctly.
+    pub unsafe fn create_event_ex(
+        &self,
+        event_ty: EventType,
+        notify_tpl: Tpl,
+        notify_fn: Option<fn(Event)>,
+        event_group: Option<&Guid>,
+    ) -> Result<OwnedEvent<'_>> {
+        // The function pointer is not present in older boot services tables
+        if self.header.revision < Revision::new(2, 0) {
+            return Err(Status::UNSUPPORTED.into());
+        }
+
+        let mut event = MaybeUninit::<Event>::uninit();
+        let (notify_func, notify_ctx) = notify_trampoline_args(notify_fn);
+        let event_group = event_group.map_or(ptr::null(), |guid| guid as *const Guid);
+
+        (self.create_event_ex)(
+            event_ty,
+            notify_tpl,
+            notify_func,
+            notify_ctx,
+            event_group,
+            event.as_mut_ptr(),
+        )
+        .into_with_val(|| OwnedEvent {
+            event: event.assume_init(),
+            boot_services: self,
+        })
     }
 
     /// Stops execution until an event is signaled
@@ -375,6 +467,10 @@ impl BootServices {
     /// To check if an event is signaled without waiting, an already signaled
     /// event can be used as the last event in the slice being checked, or the
     /// check_event() interface may be used.
+    ///
+    /// This takes raw events, so that events owned by the firmware, such as
+    /// `Input::wait_for_key_event()`, can be waited on. Use `OwnedEvent::raw`
+    /// to wait for an event created with `create_event`.
     pub fn wait_for_event(&self, events: &mut [Event]) -> Result<usize, Option<usize>> {
         let (number_of_events, events) = (events.len(), events.as_mut_ptr());
         let mut index = MaybeUninit::<usize>::uninit();
@@ -390,14 +486,51 @@ impl BootServices {
         )
     }
 
+    /// Places an event in the signaled state.
+    ///
+    /// If the event is of type `NOTIFY_SIGNAL`, its notification function is
+    /// queued at the event's notification task priority level. Signaling an
+    /// event that is already signaled has no effect.
+    pub fn signal_event(&self, event: &OwnedEvent<'_>) -> Result {
+        (self.signal_event)(event.raw()).into()
+    }
+
+    /// Checks whether an event is in the signaled state, without blocking.
+    ///
+    /// Returns `Some(())` if the event was signaled, in which case its
+    /// signaled state is cleared, and `None` otherwise. If the event is not
+    /// signaled but has a `NOTIFY_WAIT` notification function, that function
+    /// is queued and the event is checked again afterwards.
+    ///
+    /// The keyboard and pointer can be polled without waiting with
+    /// `Input::read_key()` and `Pointer::read_state()`.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidParameter` if the event is of type `NOTIFY_SIGNAL`
+    pub fn check_event(&self, event: &OwnedEvent<'_>) -> Result<Option<()>> {
+        match unsafe { (self.check_event)(event.raw()) } {
+            Status::NOT_READY => Ok(None.into()),
+            other => other.into_with_val(|| Some(())),
+        }
+    }
+
+    /// Closes an event, removing it from any event group it belongs to.
+    ///
+    /// This is equivalent to dropping the event, but allows errors to be
+    /// reported.
+    pub fn close_event(&self, event: OwnedEvent<'_>) -> Result {
+        unsafe { (self.close_event)(event.into_raw()) }.into()
+    }
+
     /// Sets the trigger for `EventType::TIMER` event.
-    pub fn set_timer(&self, event: Event, trigger_time: TimerTrigger) -> Result {
+    pub fn set_timer(&self, event: &OwnedEvent<'_>, trigger_time: TimerTrigger) -> Result {
         let (ty, time) = match trigger_time {
             TimerTrigger::Cancel => (0, 0),
             TimerTrigger::Periodic(hundreds_ns) => (1, hundreds_ns),
             TimerTrigger::Relative(hundreds_ns) => (2, hundreds_ns),
         };
-        unsafe { (self.set_timer)(event, ty, time) }.into()
+        unsafe { (self.set_timer)(event.raw(), ty, time) }.into()
     }
 
     /// Query a handle for a certain protocol.
@@ -417,23 +550,348 @@ impl BootServices {
         })
     }
 
-    /// Returns an array of handles that support the requested protocol
-    /// in a buffer allocated from pool.
+    /// Installs a protocol interface on a device handle.
+    ///
+    /// If `handle` is `None`, a new handle is created and returned. Otherwise
+    /// the interface is added to the existing handle, which is returned.
+    ///
+    /// The interface must stay valid for as long as it is installed, hence the
+    /// `'static` lifetime. A leaked `Box` or a `static` item can be used here.
+    pub fn install_protocol_interface<P: Protocol>(
+        &self,
+        handle: Option<Handle>,
+        interface: &'static mut P,
+    ) -> Result<Handle> {
+        unsafe { self.install_protocol_interface_raw(handle, &P::GUID, interface as *mut P as _) }
+    }
+
+    /// Installs an untyped protocol interface on a device handle.
     ///
     /// # Safety
     ///
-    /// This function is unsafe because the buffer has to be freed manually.
-    pub unsafe fn locate_handle_buffer(&self, search_ty: SearchType) -> Result<&[Handle]> {
-        let (ty, guid, key) = match search_ty {
-            SearchType::AllHandles => (0, ptr::null(), ptr::null_mut()),
-            SearchType::ByProtocol(guid) => (2, guid as *const _, ptr::null_mut()),
-        };
+    /// The interface must match the protocol identified by `guid` and must
+    /// remain valid for as long as it is installed.
+    unsafe fn install_protocol_interface_raw(
+        &self,
+        handle: Option<Handle>,
+        guid: &Guid,
+        interface: *mut c_void,
+    ) -> Result<Handle> {
+        let mut handle = handle.unwrap_or(Handle(ptr::null_mut()));
+        (self.install_protocol_interface)(&mut handle, guid, NATIVE_INTERFACE, interface)
+            .into_with_val(|| handle)
+    }
 
-        let mut no = 0;
+    /// Replaces a protocol interface on a device handle with a new one.
+    ///
+    /// Drivers that opened the old interface `BY_DRIVER` are disconnected and
+    /// reconnected, so that they pick up the new interface.
+    ///
+    /// # Safety
+    ///
+    /// Consumers which obtained the old interface via `handle_protocol` or
+    /// `GET_PROTOCOL` are not tracked by the firmware and will keep using it.
+    /// The caller must make sure that this does not happen before the old
+    /// interface is freed or reused.
+    pub unsafe fn reinstall_protocol_interface<P: Protocol>(
+        &self,
+        handle: Handle,
+        old_interface: *const P,
+        new_interface: &'static mut P,
+    ) -> Result {
+        (self.reinstall_protocol_interface)(
+            handle,
+            &P::GUID,
+            old_interface as *mut c_void,
+            new_interface as *mut P as *mut c_void,
+        )
+        .into()
+    }
+
+    /// Removes a protocol interface from a device handle.
+    ///
+    /// The handle itself is freed once its last protocol interface is removed.
+    ///
+    /// # Safety
+    ///
+    /// Consumers which obtained the interface via `handle_protocol` or
+    /// `GET_PROTOCOL` are not tracked by the firmware and will keep using it.
+    /// The caller must make sure that this does not happen before the
+    /// interface is freed or reused.
+    pub unsafe fn uninstall_protocol_interface<P: Protocol>(
+        &self,
+        handle: Handle,
+        interface: *const P,
+    ) -> Result {
+        (self.uninstall_protocol_interface)(handle, &P::GUID, interface as *mut c_void).into()
+    }
+
+    /// Installs several protocol interfaces on a device handle at once.
+    ///
+    /// This emulates the `InstallMultipleProtocolInterfaces` boot service:
+    /// either all interfaces are installed, or none of them is. If a new
+    /// handle would be created with a `DevicePath` that is already present
+    /// on another handle, `ALREADY_STARTED` is returned.
+    pub fn install_multiple_protocol_interfaces(
+        &self,
+        handle: Option<Handle>,
+        interfaces: &[ProtocolInterface],
+    ) -> Result<Handle> {
+        if handle.is_none() {
+            let device_path = interfaces.iter().find(|i| *i.guid == DevicePath::GUID);
+            if let Some(device_path) = device_path {
+                let mut remaining = device_path.interface as *mut DevicePath;
+                let mut existing = Handle(ptr::null_mut());
+                let status = unsafe {
+                    (self.locate_device_path)(&DevicePath::GUID, &mut remaining, &mut existing)
+                };
+                if status.is_success() && unsafe { (*remaining).is_end() } {
+                    return Err(Status::ALREADY_STARTED.into());
+                }
+            }
+        }
+
+        // The handle is only known after the first interface is installed
+        let _tpl = unsafe { self.raise_tpl(Tpl::NOTIFY) };
+        let mut handle = handle;
+        for (idx, interface) in interfaces.iter().enumerate() {
+            let result = unsafe {
+                self.install_protocol_interface_raw(handle, interface.guid, interface.interface)
+            };
+            match result {
+                Ok(completion) => handle = Some(completion.log()),
+                Err(err) => {
+                    // Roll back the interfaces that were already installed
+                    for installed in interfaces[..idx].iter().rev() {
+                        let _ = (self.uninstall_protocol_interface)(
+                            handle.unwrap(),
+                            installed.guid,
+                            installed.interface,
+                        );
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
+        handle
+            .map(Into::into)
+            .ok_or_else(|| Status::INVALID_PARAMETER.into())
+    }
+
+    /// Removes several protocol interfaces from a device handle at once.
+    ///
+    /// This emulates the `UninstallMultipleProtocolInterfaces` boot service:
+    /// either all interfaces are removed, or none of them is.
+    ///
+    /// # Safety
+    ///
+    /// The caveats of `uninstall_protocol_interface` apply to every interface.
+    pub unsafe fn uninstall_multiple_protocol_interfaces(
+        &self,
+        handle: Handle,
+        interfaces: &[ProtocolInterface],
+    ) -> Result {
+        let _tpl = self.raise_tpl(Tpl::NOTIFY);
+        for (idx, interface) in interfaces.iter().enumerate() {
+            let status =
+                (self.uninstall_protocol_interface)(handle, interface.guid, interface.interface);
+            if status.is_error() {
+                // Put back the interfaces that were already removed
+                for removed in interfaces[..idx].iter().rev() {
+                    let mut handle = handle;
+                    let _ = (self.install_protocol_interface)(
+                        &mut handle,
+                        removed.guid,
+                        NATIVE_INTERFACE,
+                        removed.interface,
+                    );
+                }
+                // The specification mandates this status for all failures
+                return Err(Status::INVALID_PARAMETER.into());
+            }
+        }
+        Status::SUCCESS.into()
+    }
+
+    /// Registers an event to be signaled whenever an interface of a certain
+    /// protocol is installed or reinstalled.
+    ///
+    /// The returned search key can be used with `SearchType::ByRegisterNotify`
+    /// or `registered_handles` to find out which handles received a new
+    /// interface. The registration is removed when the event is closed.
+    pub fn register_protocol_notify<P: Protocol>(
+        &self,
+        event: &OwnedEvent<'_>,
+    ) -> Result<ProtocolSearchKey> {
+        let mut key = MaybeUninit::<ProtocolSearchKey>::uninit();
+        (self.register_protocol_notify)(&P::GUID, event.raw(), key.as_mut_ptr())
+            .into_with_val(|| unsafe { key.assume_init() })
+    }
+
+    /// Returns an iterator over the handles that received a new interface
+    /// since the last time they were looked up with the given search key.
+    ///
+    /// Every handle is only returned once per protocol installation, so this
+    /// is typically called from the notification function of the event that
+    /// was passed to `register_protocol_notify`.
+    pub fn registered_handles(&self, key: ProtocolSearchKey) -> RegisteredHandles<'_> {
+        RegisteredHandles {
+            boot_services: self,
+            key,
+        }
+    }
+
+    /// Open a protocol interface for a handle.
+    ///
+    /// Unlike `handle_protocol`, this function records the agent (usually the
+    /// image handle of the caller) and the optional controller on whose behalf
+    /// the protocol is opened in the firmware's handle database. Depending on
+    /// the `attributes`, the firmware can then refuse to hand out the protocol
+    /// to other agents (`EXCLUSIVE`) or ask other drivers to release it first
+    /// (`BY_DRIVER`).
+    ///
+    /// The returned `ScopedProtocol` dereferences to the protocol interface and
+    /// closes it with `CloseProtocol` when dropped.
+    ///
+    /// The `TEST_PROTOCOL` attribute does not return an interface, and is
+    /// rejected with an `InvalidParameter` error: use the `test_protocol`
+    /// method instead.
+    ///
+    /// See `open_protocol_exclusive` for a safe variant.
+    ///
+    /// # Safety
+    ///
+    /// The `ScopedProtocol` hands out a mutable reference to the interface,
+    /// but only `EXCLUSIVE` and `BY_DRIVER` make the firmware refuse to open
+    /// the protocol again. With the other attributes, the caller must make
+    /// sure that no other reference to the same interface is alive while the
+    /// returned guard is, for example by not opening the protocol twice. The
+    /// firmware also does not prevent the interface from being uninstalled
+    /// while it is opened with `GET_PROTOCOL`.
+    pub unsafe fn open_protocol<P: Protocol>(
+        &self,
+        handle: Handle,
+        agent: Handle,
+        controller: Option<Handle>,
+        attributes: OpenProtocolAttributes,
+    ) -> Result<ScopedProtocol<'_, P>> {
+        if attributes.contains(OpenProtocolAttributes::TEST_PROTOCOL) {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+
+        let controller = controller.unwrap_or(Handle(ptr::null_mut()));
+        let mut interface = ptr::null_mut();
+        (self.open_protocol)(
+            handle,
+            &P::GUID,
+            &mut interface,
+            agent,
+            controller,
+            attributes.bits(),
+        )
+        .into_with_val(|| ScopedProtocol {
+            interface: &mut *(interface as *mut P),
+            boot_services: self,
+            handle,
+            agent,
+            controller,
+        })
+    }
+
+    /// Open a protocol interface for a handle with the `EXCLUSIVE` attribute.
+    ///
+    /// The firmware refuses to open the protocol again with `EXCLUSIVE` or
+    /// `BY_DRIVER` and to uninstall it while the returned `ScopedProtocol`
+    /// is alive, so the mutable access it gives is not shared. Drivers that
+    /// opened the protocol `BY_DRIVER` are disconnected first.
+    ///
+    /// # Errors
+    ///
+    /// - `AccessDenied` if the protocol is already opened exclusively
+    /// - `Unsupported` if the handle does not support the protocol
+    pub fn open_protocol_exclusive<P: Protocol>(
+        &self,
+        handle: Handle,
+        agent: Handle,
+    ) -> Result<ScopedProtocol<'_, P>> {
+        unsafe { self.open_protocol(handle, agent, None, OpenProtocolAttributes::EXCLUSIVE) }
+    }
+
+    /// Test whether a handle supports a protocol, without opening it.
+    ///
+    /// Returns an `UNSUPPORTED` error if the protocol is not installed on the
+    /// handle.
+    pub fn test_protocol<P: Protocol>(
+        &self,
+        handle: Handle,
+        agent: Handle,
+        controller: Option<Handle>,
+    ) -> Result {
+        let controller = controller.unwrap_or(Handle(ptr::null_mut()));
+        let mut interface = ptr::null_mut();
+        (self.open_protocol)(
+            handle,
+            &P::GUID,
+            &mut interface,
+            agent,
+            controller,
+            OpenProtocolAttributes::TEST_PROTOCOL.bits(),
+        )
+        .into()
+    }
+
+    /// Retrieves the list of agents that currently have a protocol interface
+    /// opened on a handle.
+    ///
+    /// The returned list is allocated from pool, and freed when dropped.
+    pub fn open_protocol_information(
+        &self,
+        handle: Handle,
+        protocol: &Guid,
+    ) -> Result<OpenProtocolInformation<'_>> {
+        let mut entries = ptr::null_mut();
+        let mut count = 0;
+        (self.open_protocol_information)(handle, protocol, &mut entries, &mut count).into_with_val(
+            || PoolSlice {
+                boot_services: self,
+                buffer: entries,
+                len: count,
+            },
+        )
+    }
+
+    /// Retrieves the GUIDs of the protocols installed on a handle.
+    ///
+    /// The returned list is allocated from pool, and freed when dropped.
+    pub fn protocols_per_handle(&self, handle: Handle) -> Result<ProtocolsPerHandle<'_>> {
+        let mut protocols = ptr::null_mut();
+        let mut count = 0;
+        (self.protocols_per_handle)(handle, &mut protocols, &mut count).into_with_val(|| {
+            PoolSlice {
+                boot_services: self,
+                buffer: protocols,
+                len: count,
+            }
+        })
+    }
+
+    /// Returns an array of handles that support the requested protocol
+    /// in a buffer allocated from pool.
+    ///
+    /// The buffer is freed when the returned `HandleBuffer` is dropped.
+    pub fn locate_handle_buffer(&self, search_ty: SearchType) -> Result<HandleBuffer<'_>> {
+        let (ty, guid, key) = search_ty.to_raw();
+
+        let mut count = 0;
         let mut buffer = ptr::null_mut();
 
-        (self.locate_handle_buffer)(ty, guid, key, &mut no, &mut buffer)
-            .into_with_val(|| core::slice::from_raw_parts(buffer, no))
+        unsafe { (self.locate_handle_buffer)(ty, guid, key, &mut count, &mut buffer) }
+            .into_with_val(|| PoolSlice {
+                boot_services: self,
+                buffer,
+                len: count,
+            })
     }
 
     /// Enumerates all handles installed on the system which match a certain query.
@@ -457,10 +915,7 @@ impl BootServices {
         };
 
         // Obtain the needed data from the parameters.
-        let (ty, guid, key) = match search_ty {
-            SearchType::AllHandles => (0, ptr::null(), ptr::null_mut()),
-            SearchType::ByProtocol(guid) => (2, guid as *const _, ptr::null_mut()),
-        };
+        let (ty, guid, key) = search_ty.to_raw();
 
         let status = unsafe { (self.locate_handle)(ty, guid, key, &mut buffer_size, buffer) };
 
@@ -492,6 +947,38 @@ impl BootServices {
         (self.disconnect_controller)(controller_handle, Handle(ptr::null_mut()), Handle(ptr::null_mut())).into()
     }
 
+    /// Adds or updates an entry of the system configuration table.
+    ///
+    /// If an entry with the same GUID already exists, its table pointer is
+    /// replaced. Otherwise, a new entry is added. The entries can then be
+    /// found with `SystemTable::config_table()`, including by the OS. Use
+    /// `remove_configuration_table` to remove an entry.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidParameter` if `table` is null
+    /// - `OutOfResources` if there is not enough memory to add the entry
+    ///
+    /// # Safety
+    ///
+    /// The table must remain valid for as long as it is installed. Tables
+    /// which must outlive boot services have to be allocated from
+    /// `MemoryType::ACPI_RECLAIM` or `MemoryType::RUNTIME_SERVICES_DATA`
+    /// memory, since other memory types may be reused by the OS.
+    pub unsafe fn install_configuration_table(&self, guid: &Guid, table: *const c_void) -> Result {
+        if table.is_null() {
+            return Err(Status::INVALID_PARAMETER.into());
+        }
+        (self.install_configuration_table)(guid, table).into()
+    }
+
+    /// Removes an entry of the system configuration table.
+    ///
+    /// Returns a `NotFound` error if there is no entry with this GUID.
+    pub fn remove_configuration_table(&self, guid: &Guid) -> Result {
+        (self.install_configuration_table)(guid, ptr::null()).into()
+    }
+
     /// Load an EFI image from a given device path.
     /// Returns a handle to the loaded image.
     pub fn load_image_from_path(
@@ -505,15 +992,65 @@ impl BootServices {
             boot_policy,
             parent_image_handle,
             device_path as *const _,
-            ptr::null_mut(),
+            ptr::null(),
             0,
             &mut handle
         ).into_with_val(|| handle)
     }
 
+    /// Load an EFI image from a buffer in memory.
+    /// Returns a handle to the loaded image.
+    ///
+    /// The image is copied by the firmware, so the buffer can be freed once
+    /// this function returns. If provided, the device path is recorded as the
+    /// `file_path` of the new image's `LoadedImage` protocol, and is also used
+    /// by the firmware to determine the security policy of the image.
+    pub fn load_image_from_buffer(
+        &self,
+        parent_image_handle: Handle,
+        source_buffer: &[u8],
+        device_path: Option<&DevicePath>,
+    ) -> Result<Handle> {
+        let device_path = device_path.map_or(ptr::null(), |p| p as *const _);
+        let mut handle = Handle(ptr::null_mut());
+        (self.load_image)(
+            false,
+            parent_image_handle,
+            device_path,
+            source_buffer.as_ptr(),
+            source_buffer.len(),
+            &mut handle,
+        )
+        .into_with_val(|| handle)
+    }
+
     /// Transfers control to a loaded image's entry point.
-    pub unsafe fn start_image(&self, image_handle: Handle) -> Result {
-        (self.start_image)(image_handle, ptr::null_mut(), ptr::null_mut()).into()
+    ///
+    /// Returns the exit status of the image, along with the exit data it
+    /// provided, if any. The exit data usually contains a description of the
+    /// reason why the image failed.
+    ///
+    /// # Safety
+    ///
+    /// The started image runs with the same privileges as the caller, and
+    /// can therefore break any invariant of the running program.
+    pub unsafe fn start_image(
+        &self,
+        image_handle: Handle,
+    ) -> Result<Option<ExitData<'_>>, Option<ExitData<'_>>> {
+        let mut exit_data_size = 0;
+        let mut exit_data = ptr::null_mut();
+        let status = (self.start_image)(image_handle, &mut exit_data_size, &mut exit_data);
+        let exit_data = NonNull::new(exit_data).map(|data| ExitData {
+            boot_services: self,
+            data,
+            size: exit_data_size,
+        });
+        if status.is_error() {
+            Err(Error::new(status, exit_data))
+        } else {
+            Ok(Completion::new(status, exit_data))
+        }
     }
 
     /// Unloads an EFI image.
@@ -526,6 +1063,43 @@ impl BootServices {
         (self.exit)(image_handle, exit_status, 0, ptr::null_mut()).into()
     }
 
+    /// Terminates a loaded EFI image, and passes a description of the exit
+    /// reason and optional binary data to the image that started it.
+    ///
+    /// As required by the UEFI specification, the exit data is copied to a
+    /// buffer allocated from pool, which will be freed by the caller of
+    /// `start_image`.
+    ///
+    /// # Safety
+    ///
+    /// This function only returns on failure. When it succeeds, the stack of
+    /// the image is discarded without running any destructor.
+    pub unsafe fn exit_with_data(
+        &self,
+        image_handle: Handle,
+        exit_status: Status,
+        description: &CStr16,
+        extra_data: &[u8],
+    ) -> Result {
+        let description = description.to_u16_slice_with_nul();
+        let description_size = mem::size_of_val(description);
+        let size = description_size + extra_data.len();
+
+        let buffer = self.allocate_pool(MemoryType::LOADER_DATA, size)?.log();
+        ptr::copy_nonoverlapping(description.as_ptr() as *const u8, buffer, description_size);
+        ptr::copy_nonoverlapping(
+            extra_data.as_ptr(),
+            buffer.add(description_size),
+            extra_data.len(),
+        );
+
+        let status = (self.exit)(image_handle, exit_status, size, buffer as *mut u16);
+
+        // We only get here if the image could not be exited
+        let _ = self.free_pool(buffer);
+        status.into()
+    }
+
     /// Locates the handle to a device on the device path that supports the specified protocol.
     pub fn locate_device_path<P: Protocol>(&self, device_path: &mut DevicePath) -> Result<Handle> {
         unsafe {
@@ -554,6 +1128,21 @@ impl BootServices {
         (self.exit_boot_services)(image, mmap_key).into()
     }
 
+    /// Returns the next value of the platform's monotonic counter.
+    ///
+    /// The upper 32 bits of the counter are the platform's high monotonic
+    /// count, which persists across resets and is incremented on every boot,
+    /// or when the lower 32 bits overflow. It is also available from
+    /// `RuntimeServices::get_next_high_monotonic_count`.
+    ///
+    /// # Errors
+    ///
+    /// - `DeviceError` if the counter overflowed or the device failed
+    pub fn get_next_monotonic_count(&self) -> Result<u64> {
+        let mut count = 0;
+        (self.get_next_monotonic_count)(&mut count).into_with_val(|| count)
+    }
+
     /// Stalls the processor for an amount of time.
     ///
     /// The time is in microseconds.
@@ -614,6 +1203,17 @@ impl BootServices {
         })
     }
 
+    /// Computes the CRC32 checksum of some data, using the firmware's
+    /// implementation.
+    ///
+    /// The `table::crc32` function can be used when boot services are not
+    /// available.
+    pub fn calculate_crc32(&self, data: &[u8]) -> Result<u32> {
+        let mut crc = 0;
+        (self.calculate_crc32)(data.as_ptr() as *const c_void, data.len(), &mut crc)
+            .into_with_val(|| crc)
+    }
+
     /// Copies memory from source to destination. The buffers can overlap.
     ///
     /// # Safety
@@ -666,6 +1266,25 @@ impl BootServices {
             .map(|completion| completion.with_status(status2))
     }
 
+    /// Retrieves the protocols installed on every handle of the system.
+    ///
+    /// This is mostly useful for diagnostics, such as listing the handle
+    /// database in the style of the UEFI shell's `dh` command.
+    pub fn protocols_by_handle(&self) -> Result<Vec<(Handle, Vec<Guid>)>> {
+        let (status, handles) = self.locate_handle_buffer(SearchType::AllHandles)?.split();
+
+        let mut result = Vec::with_capacity(handles.len());
+        for &handle in handles.iter() {
+            // Handles can disappear while we are iterating, skip those
+            if let Ok(protocols) = self.protocols_per_handle(handle) {
+                let protocols = protocols.log().iter().map(|&guid| *guid).collect();
+                result.push((handle, protocols));
+            }
+        }
+
+        Ok(Completion::new(status, result))
+    }
+
     /// Retrieves the `SimpleFileSystem` protocol associated with
     /// the device the given image was loaded from.
     ///
@@ -740,6 +1359,261 @@ impl Drop for TplGuard<'_> {
     }
 }
 
+bitflags! {
+    /// Flags describing how a protocol is opened with `BootServices::open_protocol`.
+    pub struct OpenProtocolAttributes: u32 {
+        /// Used in the implementation of `handle_protocol`.
+        const BY_HANDLE_PROTOCOL = 0x01;
+        /// Used by a driver to get a protocol interface from a handle.
+        ///
+        /// Care must be taken when using this attribute, since the firmware
+        /// does not prevent the protocol from being removed while it is open.
+        const GET_PROTOCOL = 0x02;
+        /// Used by a driver to test for the existence of a protocol interface.
+        const TEST_PROTOCOL = 0x04;
+        /// Used by bus drivers to show that a protocol interface is being used
+        /// by one of the child controllers of a bus.
+        const BY_CHILD_CONTROLLER = 0x08;
+        /// Used by a driver to gain access to a protocol interface.
+        ///
+        /// Other drivers opening the protocol with this attribute will fail
+        /// with `ACCESS_DENIED` or `ALREADY_STARTED`.
+        const BY_DRIVER = 0x10;
+        /// Used by applications to gain exclusive access to a protocol
+        /// interface. Drivers that opened the protocol `BY_DRIVER` are
+        /// disconnected first.
+        const EXCLUSIVE = 0x20;
+        /// Used by a driver to gain exclusive access to a protocol interface.
+        const BY_DRIVER_EXCLUSIVE = Self::BY_DRIVER.bits | Self::EXCLUSIVE.bits;
+    }
+}
+
+/// The only interface type defined by the UEFI specification.
+const NATIVE_INTERFACE: u32 = 0;
+
+/// A type-erased protocol interface, used to install or uninstall several
+/// protocols at once.
+#[derive(Debug, Copy, Clone)]
+pub struct ProtocolInterface {
+    guid: &'static Guid,
+    interface: *mut c_void,
+}
+
+impl ProtocolInterface {
+    /// Wraps a protocol interface that is to be installed.
+    pub fn new<P: Protocol>(interface: &'static mut P) -> Self {
+        ProtocolInterface {
+            guid: &P::GUID,
+            interface: interface as *mut P as *mut c_void,
+        }
+    }
+
+    /// Wraps a protocol interface that is currently installed, so that it
+    /// can be passed to `BootServices::uninstall_multiple_protocol_interfaces`.
+    pub fn from_ptr<P: Protocol>(interface: *const P) -> Self {
+        ProtocolInterface {
+            guid: &P::GUID,
+            interface: interface as *mut c_void,
+        }
+    }
+}
+
+/// RAII guard for a protocol opened with `BootServices::open_protocol`
+///
+/// Dereferences to the protocol interface, and will automatically close the
+/// protocol when dropped.
+pub struct ScopedProtocol<'boot, P: Protocol> {
+    interface: &'boot mut P,
+    boot_services: &'boot BootServices,
+    handle: Handle,
+    agent: Handle,
+    controller: Handle,
+}
+
+impl<P: Protocol> Deref for ScopedProtocol<'_, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        self.interface
+    }
+}
+
+impl<P: Protocol> DerefMut for ScopedProtocol<'_, P> {
+    fn deref_mut(&mut self) -> &mut P {
+        self.interface
+    }
+}
+
+impl<P: Protocol> Drop for ScopedProtocol<'_, P> {
+    fn drop(&mut self) {
+        let status =
+            (self.boot_services.close_protocol)(self.handle, &P::GUID, self.agent, self.controller);
+        // CloseProtocol fails with NOT_FOUND if the interface was uninstalled
+        // or reinstalled while it was open. There is nothing left to close
+        // then, and no way to report the error from here.
+        if status != Status::SUCCESS {
+            warn!("Failed to close protocol {}: {:?}", P::GUID, status);
+        }
+    }
+}
+
+/// An event created with `BootServices::create_event`
+///
+/// The event is closed when this is dropped.
+pub struct OwnedEvent<'boot> {
+    event: Event,
+    boot_services: &'boot BootServices,
+}
+
+impl<'boot> OwnedEvent<'boot> {
+    /// Takes back ownership of an event returned by `into_raw`, so that it
+    /// is closed when dropped.
+    ///
+    /// # Safety
+    ///
+    /// `event` must have been created with `create_event` or
+    /// `create_event_ex`, must not have been closed, and must not be owned by
+    /// another `OwnedEvent`.
+    pub unsafe fn from_raw(boot_services: &'boot BootServices, event: Event) -> Self {
+        OwnedEvent {
+            event,
+            boot_services,
+        }
+    }
+
+    /// Returns the raw handle of the event, without giving up ownership.
+    ///
+    /// This is needed to wait for the event with `wait_for_event`. The raw
+    /// handle is a copy which is not tied to this event, and must not be
+    /// used anymore once the event is closed.
+    pub fn raw(&self) -> Event {
+        self.event
+    }
+
+    /// Consumes this event without closing it, and returns the raw handle.
+    ///
+    /// This is useful for events which must remain alive until boot services
+    /// are exited, such as `SIGNAL_EXIT_BOOT_SERVICES` notifications.
+    pub fn into_raw(self) -> Event {
+        let event = self.event;
+        mem::forget(self);
+        event
+    }
+}
+
+impl Drop for OwnedEvent<'_> {
+    fn drop(&mut self) {
+        // CloseEvent cannot fail on an event which was created by CreateEvent
+        let _ = unsafe { (self.boot_services.close_event)(self.event) };
+    }
+}
+
+/// Exit data returned by an image started with `BootServices::start_image`
+///
+/// The exit data consists of a null-terminated description string, which may
+/// be followed by binary data. It is freed when this is dropped.
+pub struct ExitData<'boot> {
+    boot_services: &'boot BootServices,
+    data: NonNull<u16>,
+    size: usize,
+}
+
+impl ExitData<'_> {
+    /// Returns the whole exit data as raw bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const u8, self.size) }
+    }
+
+    /// Returns the description of the exit reason, if the exit data starts
+    /// with a valid null-terminated UCS-2 string.
+    pub fn description(&self) -> Option<&CStr16> {
+        let codes =
+            unsafe { slice::from_raw_parts(self.data.as_ptr(), self.size / mem::size_of::<u16>()) };
+        let len = codes.iter().position(|&c| c == 0)?;
+        CStr16::from_u16_with_nul(&codes[..=len]).ok()
+    }
+
+    /// Returns the binary data following the description, if any
+    pub fn extra_data(&self) -> &[u8] {
+        let offset = self
+            .description()
+            .map_or(self.size, |d| mem::size_of_val(d.to_u16_slice_with_nul()));
+        &self.as_bytes()[offset..]
+    }
+}
+
+impl fmt::Debug for ExitData<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExitData")
+            .field("description", &self.description())
+            .field("extra_data", &self.extra_data())
+            .finish()
+    }
+}
+
+impl Drop for ExitData<'_> {
+    fn drop(&mut self) {
+        let _ = self.boot_services.free_pool(self.data.as_ptr() as *mut u8);
+    }
+}
+
+/// A slice allocated from pool by the firmware
+///
+/// Dereferences to the slice, and frees the underlying buffer when dropped.
+pub struct PoolSlice<'boot, T> {
+    boot_services: &'boot BootServices,
+    buffer: *mut T,
+    len: usize,
+}
+
+impl<T> Deref for PoolSlice<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        if self.buffer.is_null() {
+            &[]
+        } else {
+            unsafe { slice::from_raw_parts(self.buffer, self.len) }
+        }
+    }
+}
+
+impl<T> Drop for PoolSlice<'_, T> {
+    fn drop(&mut self) {
+        if !self.buffer.is_null() {
+            let _ = self.boot_services.free_pool(self.buffer as *mut u8);
+        }
+    }
+}
+
+/// List of handles returned by `BootServices::locate_handle_buffer`
+pub type HandleBuffer<'boot> = PoolSlice<'boot, Handle>;
+
+/// Information about an agent which has opened a protocol interface
+///
+/// Returned by `BootServices::open_protocol_information`.
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+pub struct OpenProtocolInformationEntry {
+    /// The image handle of the agent that opened the protocol.
+    pub agent_handle: Handle,
+    /// The controller handle on behalf of which the protocol was opened, or a
+    /// null handle if none was specified.
+    pub controller_handle: Handle,
+    /// The attributes the protocol was opened with.
+    pub attributes: OpenProtocolAttributes,
+    /// The number of times the protocol was opened by this agent.
+    pub open_count: u32,
+}
+
+/// List of the agents that opened a protocol interface, returned by
+/// `BootServices::open_protocol_information`
+pub type OpenProtocolInformation<'boot> = PoolSlice<'boot, OpenProtocolInformationEntry>;
+
+/// List of the protocols installed on a handle, returned by
+/// `BootServices::protocols_per_handle`
+pub type ProtocolsPerHandle<'boot> = PoolSlice<'boot, &'boot Guid>;
+
 /// Type of allocation to perform.
 #[derive(Debug, Copy, Clone)]
 pub enum AllocateType {
@@ -809,6 +1683,10 @@ impl MemoryType {
 /// Memory descriptor version number
 pub const MEMORY_DESCRIPTOR_VERSION: u32 = 1;
 
+/// Size of the pages allocated by `allocate_pages` and counted by
+/// `MemoryDescriptor::page_count`
+pub const PAGE_SIZE: usize = 4096;
+
 /// A structure describing a region of memory.
 #[derive(Debug, Copy, Clone)]
 #[repr(C)]
@@ -929,7 +1807,11 @@ pub enum SearchType<'guid> {
     /// If the protocol implements the `Protocol` interface,
     /// you can use the `from_proto` function to construct a new `SearchType`.
     ByProtocol(&'guid Guid),
-    // TODO: add ByRegisterNotify once the corresponding function is implemented.
+    /// Returns the next handle that received a new interface for a protocol
+    /// notification registered with `BootServices::register_protocol_notify`.
+    ///
+    /// Only one handle is returned per search.
+    ByRegisterNotify(ProtocolSearchKey),
 }
 
 impl<'guid> SearchType<'guid> {
@@ -937,6 +1819,48 @@ impl<'guid> SearchType<'guid> {
     pub fn from_proto<P: Protocol>() -> Self {
         SearchType::ByProtocol(&P::GUID)
     }
+
+    /// Returns the raw search type, protocol and search key
+    fn to_raw(self) -> (i32, *const Guid, *mut c_void) {
+        match self {
+            SearchType::AllHandles => (0, ptr::null(), ptr::null_mut()),
+            SearchType::ByRegisterNotify(key) => (1, ptr::null(), key.0.as_ptr()),
+            SearchType::ByProtocol(guid) => (2, guid as *const _, ptr::null_mut()),
+        }
+    }
+}
+
+/// Opaque key returned by `BootServices::register_protocol_notify`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct ProtocolSearchKey(NonNull<c_void>);
+
+/// An iterator over the handles with new protocol interfaces for a
+/// protocol notification registration
+///
+/// Returned by `BootServices::registered_handles`.
+pub struct RegisteredHandles<'boot> {
+    boot_services: &'boot BootServices,
+    key: ProtocolSearchKey,
+}
+
+impl Iterator for RegisteredHandles<'_> {
+    type Item = Handle;
+
+    fn next(&mut self) -> Option<Handle> {
+        let mut handle = [Handle(ptr::null_mut())];
+        // Any error, including `NOT_FOUND`, means that there are no new handles
+        let found = self
+            .boot_services
+            .locate_handle(SearchType::ByRegisterNotify(self.key), Some(&mut handle))
+            .ok()?
+            .log();
+        if found > 0 {
+            Some(handle[0])
+        } else {
+            None
+        }
+    }
 }
 
 bitflags! {
@@ -975,6 +1899,75 @@ bitflags! {
 /// Raw event notification function
 type EventNotifyFn = unsafe extern "efiapi" fn(event: Event, context: *mut c_void);
 
+/// Builds the raw notification function and context for a Rust callback
+fn notify_trampoline_args(notify_fn: Option<fn(Event)>) -> (Option<EventNotifyFn>, *mut c_void) {
+    // Use a trampoline to handle the impedance mismatch between Rust & C
+    unsafe extern "efiapi" fn notify_trampoline(e: Event, ctx: *mut c_void) {
+        let notify_fn: fn(Event) = mem::transmute(ctx);
+        notify_fn(e); // SAFETY: Aborting panics are assumed here
+    }
+    notify_fn
+        .map(|notify_fn| {
+            (
+                Some(notify_trampoline as EventNotifyFn),
+                notify_fn as fn(Event) as *mut c_void,
+            )
+        })
+        .unwrap_or((None, ptr::null_mut()))
+}
+
+/// Event group which is notified when `ExitBootServices` is invoked.
+///
+/// This is the event group equivalent of `EventType::SIGNAL_EXIT_BOOT_SERVICES`.
+pub const EVENT_GROUP_EXIT_BOOT_SERVICES: Guid = Guid::from_values(
+    0x27abf055,
+    0xb1b8,
+    0x4c26,
+    0x8048,
+    [0x74, 0x8f, 0x37, 0xba, 0xa2, 0xdf],
+);
+
+/// Event group which is notified when `SetVirtualAddressMap` is invoked.
+///
+/// This is the event group equivalent of
+/// `EventType::SIGNAL_VIRTUAL_ADDRESS_CHANGE`.
+pub const EVENT_GROUP_VIRTUAL_ADDRESS_CHANGE: Guid = Guid::from_values(
+    0x13fa7698,
+    0xc831,
+    0x49c7,
+    0x87ea,
+    [0x8f, 0x43, 0xfc, 0xc2, 0x51, 0x96],
+);
+
+/// Event group which is notified whenever the memory map changes.
+pub const EVENT_GROUP_MEMORY_MAP_CHANGE: Guid = Guid::from_values(
+    0x78bee926,
+    0x692f,
+    0x48fd,
+    0x9edb,
+    [0x01, 0x42, 0x2e, 0xf0, 0xd7, 0xab],
+);
+
+/// Event group which is notified when the boot manager is about to load and
+/// execute a boot option.
+pub const EVENT_GROUP_READY_TO_BOOT: Guid = Guid::from_values(
+    0x7ce88fb3,
+    0x4bd7,
+    0x4679,
+    0x87a8,
+    [0xa8, 0xd8, 0xde, 0xe5, 0x0d, 0x2b],
+);
+
+/// Event group which is notified when `ResetSystem` is invoked, before the
+/// platform is actually reset.
+pub const EVENT_GROUP_RESET_SYSTEM: Guid = Guid::from_values(
+    0x62da6a56,
+    0x13fb,
+    0x485a,
+    0xa8da,
+    [0xa3, 0xdd, 0x79, 0x12, 0xcb, 0x6b],
+);
+
 /// Timer events manipulation
 pub enum TimerTrigger {
     /// Cancel event's timer
@@ -0,0 +1,44 @@
+//! Software implementation of the CRC32 checksum used by UEFI.
+//!
+//! UEFI uses the standard CRC32 algorithm (as used by Ethernet and zlib) to
+//! protect its tables. The firmware exposes it as the `CalculateCrc32` boot
+//! service, but that one is not available after exiting boot services, or
+//! when running outside of UEFI altogether.
+
+/// Lookup table for the reflected CRC32 polynomial
+const TABLE: [u32; 256] = make_table();
+
+const fn make_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Continues a CRC32 computation over more data.
+///
+/// The computation must be started with a `crc` of 0. This allows computing
+/// the checksum of data that is not contiguous in memory.
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    !data.iter().fold(!crc, |crc, &byte| {
+        TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// Computes the CRC32 checksum of some data.
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
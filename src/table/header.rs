@@ -1,4 +1,5 @@
-use super::Revision;
+use super::{crc32_update, Revision, Table};
+use core::{mem, slice};
 
 /// All standard UEFI tables begin with a common header.
 #[derive(Debug)]
@@ -10,9 +11,69 @@ pub struct Header {
     pub revision: Revision,
     /// The size in bytes of the entire table.
     pub size: u32,
-    /// 32-bit CRC-32-Castagnoli of the entire table,
+    /// 32-bit CRC of the entire table,
     /// calculated with this field set to 0.
     pub crc: u32,
     /// Reserved field that must be set to 0.
     _reserved: u32,
 }
+
+impl Header {
+    /// Checks that this header belongs to a valid table of type `T`.
+    ///
+    /// The signature must match `T::SIGNATURE`, the revision must be a valid
+    /// UEFI revision, and the CRC of the whole table must match the one stored
+    /// in the header. The CRC is computed in software, so this can be used
+    /// after exiting boot services or outside of UEFI.
+    ///
+    /// # Safety
+    ///
+    /// The header must be at the beginning of a table that is at least `size`
+    /// bytes long.
+    pub unsafe fn validate<T: Table>(&self) -> Result<(), HeaderError> {
+        if self.signature != T::SIGNATURE {
+            return Err(HeaderError::InvalidSignature(self.signature));
+        }
+        if self.revision.major() == 0 || self.revision.minor() > 99 {
+            return Err(HeaderError::InvalidRevision(self.revision));
+        }
+        if (self.size as usize) < mem::size_of::<Header>() {
+            return Err(HeaderError::InvalidSize(self.size));
+        }
+
+        // The CRC is computed with the CRC field itself set to 0
+        let table = slice::from_raw_parts(self as *const _ as *const u8, self.size as usize);
+        let crc_offset = 16;
+        let crc_end = crc_offset + mem::size_of::<u32>();
+        let mut crc = crc32_update(0, &table[..crc_offset]);
+        crc = crc32_update(crc, &[0; 4]);
+        crc = crc32_update(crc, &table[crc_end..]);
+
+        if crc == self.crc {
+            Ok(())
+        } else {
+            Err(HeaderError::InvalidCrc {
+                expected: self.crc,
+                actual: crc,
+            })
+        }
+    }
+}
+
+/// Errors that can be detected when validating a table header
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HeaderError {
+    /// The signature does not match the expected table type.
+    InvalidSignature(u64),
+    /// The revision is not a valid UEFI revision.
+    InvalidRevision(Revision),
+    /// The table size is smaller than the header.
+    InvalidSize(u32),
+    /// The CRC of the table does not match the one stored in the header.
+    InvalidCrc {
+        /// The CRC stored in the header.
+        expected: u32,
+        /// The CRC computed over the table.
+        actual: u32,
+    },
+}
@@ -7,8 +7,11 @@ pub trait Table {
     const SIGNATURE: u64;
 }
 
+mod crc;
+pub use self::crc::{crc32, crc32_update};
+
 mod header;
-pub use self::header::Header;
+pub use self::header::{Header, HeaderError};
 
 mod revision;
 pub use self::revision::Revision;
@@ -21,4 +24,6 @@ pub mod runtime;
 
 pub mod cfg;
 
+pub mod acpi;
+
 pub mod dxe;
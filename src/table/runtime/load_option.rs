@@ -0,0 +1,438 @@
+//! Load options, and the global variables describing the boot order.
+
+use super::{RuntimeServices, Variable, VariableAttributes};
+use crate::alloc_api::{format, string::String, vec::Vec};
+use crate::result::Error;
+use crate::{Result, ResultExt, Status};
+use bitflags::bitflags;
+use core::convert::TryFrom;
+
+bitflags! {
+    /// Attributes of a load option
+    pub struct LoadOptionAttributes: u32 {
+        /// The option is considered by the boot manager.
+        const ACTIVE = 0x0000_0001;
+        /// All drivers are disconnected and reconnected after the option is
+        /// loaded, if it is a driver option.
+        const FORCE_RECONNECT = 0x0000_0002;
+        /// The option is not displayed in boot menus.
+        const HIDDEN = 0x0000_0008;
+        /// Mask of the option's category.
+        const CATEGORY = 0x0000_1F00;
+        /// The option is an application, which is only launched explicitly.
+        const CATEGORY_APP = 0x0000_0100;
+    }
+}
+
+impl LoadOptionAttributes {
+    /// The option's category, selected by the bits under `CATEGORY`
+    pub fn category(self) -> LoadOptionCategory {
+        LoadOptionCategory((self & Self::CATEGORY).bits())
+    }
+}
+
+newtype_enum! {
+/// Category of a load option, which tells the boot manager how to use it
+pub enum LoadOptionCategory: u32 => {
+    /// The option is part of the normal boot process.
+    BOOT = 0x0000_0000,
+    /// The option is an application, which is only launched explicitly.
+    APP  = 0x0000_0100,
+}}
+
+/// Errors which can occur when parsing or building a `LoadOption`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum LoadOptionError {
+    /// The load option is shorter than its fixed-size fields.
+    TooShort,
+    /// The description is not NUL-terminated.
+    UnterminatedDescription,
+    /// The description cannot be represented as UCS-2, or contains a NUL.
+    InvalidDescription,
+    /// The device path list is malformed, or overruns the load option.
+    InvalidFilePathList,
+}
+
+/// Size of the device path node header: type, sub-type and length.
+const NODE_HEADER_SIZE: usize = 4;
+
+/// Device path type and sub-types ending a device path instance.
+const END_TYPE: u8 = 0x7f;
+const END_INSTANCE_SUBTYPE: u8 = 0x01;
+const END_ENTIRE_SUBTYPE: u8 = 0xff;
+
+/// An `EFI_LOAD_OPTION`, as stored in `Boot####` and `Driver####` variables.
+///
+/// The device paths are kept as raw bytes, since the load option's data is
+/// not aligned.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LoadOption {
+    attributes: LoadOptionAttributes,
+    description: String,
+    file_path_list: Vec<u8>,
+    optional_data: Vec<u8>,
+}
+
+impl LoadOption {
+    /// Builds a load option
+    ///
+    /// The `file_path_list` must contain one or more device path instances,
+    /// the last of which is terminated by an end-of-entire-path node.
+    pub fn new(
+        attributes: LoadOptionAttributes,
+        description: &str,
+        file_path_list: Vec<u8>,
+        optional_data: Vec<u8>,
+    ) -> core::result::Result<Self, LoadOptionError> {
+        if description.contains('\0') || ucs2::encode_with(description, |_| Ok(())).is_err() {
+            return Err(LoadOptionError::InvalidDescription);
+        }
+        if file_path_list.len() > usize::from(u16::MAX) {
+            return Err(LoadOptionError::InvalidFilePathList);
+        }
+        validate_file_path_list(&file_path_list)?;
+
+        Ok(LoadOption {
+            attributes,
+            description: description.into(),
+            file_path_list,
+            optional_data,
+        })
+    }
+
+    /// Parses a load option from the contents of a variable
+    pub fn parse(data: &[u8]) -> core::result::Result<Self, LoadOptionError> {
+        if data.len() < 6 {
+            return Err(LoadOptionError::TooShort);
+        }
+        let attributes = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+        let file_path_list_length = usize::from(u16::from_le_bytes([data[4], data[5]]));
+
+        // The description is a NUL-terminated UCS-2 string.
+        let rest = &data[6..];
+        let mut description = Vec::new();
+        let mut description_size = None;
+        for (i, chunk) in rest.chunks_exact(2).enumerate() {
+            let c = u16::from_le_bytes([chunk[0], chunk[1]]);
+            if c == 0 {
+                description_size = Some((i + 1) * 2);
+                break;
+            }
+            description.push(c);
+        }
+        let description_size = description_size.ok_or(LoadOptionError::UnterminatedDescription)?;
+
+        let mut utf8 = Vec::with_capacity(description.len());
+        ucs2::decode_with(&description, |bytes| {
+            utf8.extend_from_slice(bytes);
+            Ok(())
+        })
+        .map_err(|_| LoadOptionError::InvalidDescription)?;
+        let description =
+            String::from_utf8(utf8).map_err(|_| LoadOptionError::InvalidDescription)?;
+
+        let rest = &rest[description_size..];
+        if rest.len() < file_path_list_length {
+            return Err(LoadOptionError::InvalidFilePathList);
+        }
+        let (file_path_list, optional_data) = rest.split_at(file_path_list_length);
+        validate_file_path_list(file_path_list)?;
+
+        Ok(LoadOption {
+            // Reserved and category bits must survive rewriting the option
+            attributes: unsafe { LoadOptionAttributes::from_bits_unchecked(attributes) },
+            description,
+            file_path_list: file_path_list.into(),
+            optional_data: optional_data.into(),
+        })
+    }
+
+    /// Serializes the load option, so that it can be stored in a variable
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&self.attributes.bits().to_le_bytes());
+        // `new` and `parse` check that the length fits in 16 bits.
+        data.extend_from_slice(&(self.file_path_list.len() as u16).to_le_bytes());
+        // `new` and `parse` check that the description can be encoded.
+        let _ = ucs2::encode_with(&self.description, |c| {
+            data.extend_from_slice(&c.to_le_bytes());
+            Ok(())
+        });
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(&self.file_path_list);
+        data.extend_from_slice(&self.optional_data);
+        data
+    }
+
+    /// The option's attributes
+    pub fn attributes(&self) -> LoadOptionAttributes {
+        self.attributes
+    }
+
+    /// Changes the option's attributes
+    pub fn set_attributes(&mut self, attributes: LoadOptionAttributes) {
+        self.attributes = attributes;
+    }
+
+    /// The description of the option, which is displayed to the user
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// All the device path instances of the option, including their end
+    /// nodes
+    ///
+    /// The first instance points to the device or file which is loaded.
+    pub fn file_path_list(&self) -> &[u8] {
+        &self.file_path_list
+    }
+
+    /// Iterates over the device path instances of the option, without their
+    /// end nodes
+    pub fn file_paths(&self) -> FilePaths<'_> {
+        FilePaths {
+            remaining: &self.file_path_list,
+        }
+    }
+
+    /// The data which is passed to the loaded image
+    pub fn optional_data(&self) -> &[u8] {
+        &self.optional_data
+    }
+}
+
+/// Checks that a device path list consists of well-formed nodes, and ends
+/// with an end-of-entire-path node.
+fn validate_file_path_list(mut list: &[u8]) -> core::result::Result<(), LoadOptionError> {
+    loop {
+        if list.len() < NODE_HEADER_SIZE {
+            return Err(LoadOptionError::InvalidFilePathList);
+        }
+        let length = usize::from(u16::from_le_bytes([list[2], list[3]]));
+        if length < NODE_HEADER_SIZE || length > list.len() {
+            return Err(LoadOptionError::InvalidFilePathList);
+        }
+        if list[0] == END_TYPE && list[1] == END_ENTIRE_SUBTYPE {
+            return if length == list.len() {
+                Ok(())
+            } else {
+                Err(LoadOptionError::InvalidFilePathList)
+            };
+        }
+        list = &list[length..];
+    }
+}
+
+/// Iterator over the device path instances of a `LoadOption`
+#[derive(Debug, Clone)]
+pub struct FilePaths<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> Iterator for FilePaths<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        // The list was validated when the load option was built.
+        let list = self.remaining;
+        let mut offset = 0;
+        while offset + NODE_HEADER_SIZE <= list.len() {
+            let node = &list[offset..];
+            let length = usize::from(u16::from_le_bytes([node[2], node[3]]));
+            if node[0] == END_TYPE
+                && (node[1] == END_INSTANCE_SUBTYPE || node[1] == END_ENTIRE_SUBTYPE)
+            {
+                self.remaining = &list[offset + length..];
+                return Some(&list[..offset]);
+            }
+            offset += length;
+        }
+        None
+    }
+}
+
+/// Attributes of the boot manager's global variables
+const BOOT_VARIABLE_ATTRIBUTES: VariableAttributes = VariableAttributes::from_bits_truncate(
+    VariableAttributes::NON_VOLATILE.bits()
+        | VariableAttributes::BOOTSERVICE_ACCESS.bits()
+        | VariableAttributes::RUNTIME_ACCESS.bits(),
+);
+
+/// Name of the `Boot####` variable holding a boot option
+fn boot_option_name(index: u16) -> String {
+    format!("Boot{:04X}", index)
+}
+
+/// Parses a variable holding a single `UINT16`
+fn parse_u16(data: &[u8]) -> Result<u16> {
+    <[u8; 2]>::try_from(data)
+        .map(u16::from_le_bytes)
+        .map(Into::into)
+        .map_err(|_| Status::COMPROMISED_DATA.into())
+}
+
+impl RuntimeServices {
+    /// Reads the `BootOrder` variable, which lists the boot options in the
+    /// order in which they are tried
+    pub fn boot_order(&self) -> Result<Vec<u16>> {
+        let (data, _) = self
+            .get_variable("BootOrder", &Variable::GLOBAL_VARIABLE_GUID)?
+            .log();
+        if data.len() % 2 != 0 {
+            return Err(Status::COMPROMISED_DATA.into());
+        }
+        Ok(data
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    /// Writes the `BootOrder` variable
+    pub fn set_boot_order(&self, boot_order: &[u16]) -> Result {
+        let mut data = Vec::with_capacity(boot_order.len() * 2);
+        for index in boot_order {
+            data.extend_from_slice(&index.to_le_bytes());
+        }
+        self.set_variable(
+            "BootOrder",
+            &Variable::GLOBAL_VARIABLE_GUID,
+            BOOT_VARIABLE_ATTRIBUTES,
+            &data,
+        )
+    }
+
+    /// Reads the `BootNext` variable, which holds the boot option tried
+    /// first on the next boot only
+    ///
+    /// Returns a `NotFound` error if it is not set.
+    pub fn boot_next(&self) -> Result<u16> {
+        let (data, _) = self
+            .get_variable("BootNext", &Variable::GLOBAL_VARIABLE_GUID)?
+            .log();
+        parse_u16(&data)
+    }
+
+    /// Writes the `BootNext` variable
+    pub fn set_boot_next(&self, index: u16) -> Result {
+        self.set_variable(
+            "BootNext",
+            &Variable::GLOBAL_VARIABLE_GUID,
+            BOOT_VARIABLE_ATTRIBUTES,
+            &index.to_le_bytes(),
+        )
+    }
+
+    /// Deletes the `BootNext` variable
+    pub fn delete_boot_next(&self) -> Result {
+        self.set_variable(
+            "BootNext",
+            &Variable::GLOBAL_VARIABLE_GUID,
+            VariableAttributes::empty(),
+            &[],
+        )
+    }
+
+    /// Reads the `BootCurrent` variable, which holds the boot option that
+    /// was selected for the current boot
+    pub fn boot_current(&self) -> Result<u16> {
+        let (data, _) = self
+            .get_variable("BootCurrent", &Variable::GLOBAL_VARIABLE_GUID)?
+            .log();
+        parse_u16(&data)
+    }
+
+    /// Reads and parses the `Boot####` variable with the given index
+    ///
+    /// Parsing errors are reported as `CompromisedData` errors, which carry
+    /// the cause of the error.
+    pub fn boot_option(&self, index: u16) -> Result<LoadOption, Option<LoadOptionError>> {
+        let (data, _) = self
+            .get_variable(&boot_option_name(index), &Variable::GLOBAL_VARIABLE_GUID)
+            .map_err(|err| Error::new(err.status(), None))?
+            .log();
+        LoadOption::parse(&data)
+            .map(Into::into)
+            .map_err(|err| Error::new(Status::COMPROMISED_DATA, Some(err)))
+    }
+
+    /// Writes the `Boot####` variable with the given index
+    ///
+    /// This does not change the `BootOrder`.
+    pub fn set_boot_option(&self, index: u16, option: &LoadOption) -> Result {
+        self.set_variable(
+            &boot_option_name(index),
+            &Variable::GLOBAL_VARIABLE_GUID,
+            BOOT_VARIABLE_ATTRIBUTES,
+            &option.to_bytes(),
+        )
+    }
+
+    /// Deletes the `Boot####` variable with the given index
+    ///
+    /// This does not change the `BootOrder`.
+    pub fn delete_boot_option(&self, index: u16) -> Result {
+        self.set_variable(
+            &boot_option_name(index),
+            &Variable::GLOBAL_VARIABLE_GUID,
+            VariableAttributes::empty(),
+            &[],
+        )
+    }
+
+    /// Creates a boot option with the first free index, and puts it first in
+    /// the `BootOrder`
+    ///
+    /// Returns the index of the new boot option.
+    ///
+    /// # Errors
+    ///
+    /// - `OutOfResources` if all the indices are in use
+    pub fn create_boot_option(&self, option: &LoadOption) -> Result<u16> {
+        let mut boot_order = match self.boot_order() {
+            Ok(boot_order) => boot_order.log(),
+            Err(err) if err.status() == Status::NOT_FOUND => Vec::new(),
+            Err(err) => return Err(err),
+        };
+
+        let mut index = None;
+        for candidate in 0..=u16::MAX {
+            if boot_order.contains(&candidate) {
+                continue;
+            }
+            match self.get_variable(
+                &boot_option_name(candidate),
+                &Variable::GLOBAL_VARIABLE_GUID,
+            ) {
+                Ok(_) => continue,
+                Err(err) if err.status() == Status::NOT_FOUND => {
+                    index = Some(candidate);
+                    break;
+                }
+                Err(err) => return Err(err.status().into()),
+            }
+        }
+        let index = index.ok_or(Status::OUT_OF_RESOURCES)?;
+
+        self.set_boot_option(index, option)?.log();
+        boot_order.insert(0, index);
+        self.set_boot_order(&boot_order).map_inner(|_| index)
+    }
+
+    /// Deletes a boot option, and removes it from the `BootOrder`
+    pub fn remove_boot_option(&self, index: u16) -> Result {
+        match self.boot_order() {
+            Ok(boot_order) => {
+                let boot_order = boot_order.log();
+                if boot_order.contains(&index) {
+                    let boot_order: Vec<u16> =
+                        boot_order.into_iter().filter(|&i| i != index).collect();
+                    self.set_boot_order(&boot_order)?.log();
+                }
+            }
+            Err(err) if err.status() == Status::NOT_FOUND => {}
+            Err(err) => return Err(err),
+        }
+        self.delete_boot_option(index)
+    }
+}
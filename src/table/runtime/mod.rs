@@ -0,0 +1,1084 @@
+//! UEFI services available at runtime, even after the OS boots.
+
+use super::{Header, Revision};
+use crate::table::boot::{AllocateType, BootServices, MemoryDescriptor, MemoryType, PAGE_SIZE};
+#[cfg(feature = "exts")]
+use crate::result::Error;
+use crate::{Result, ResultExt, Status, Guid};
+use crate::data_types::{Char16, CStr16};
+use bitflags::bitflags;
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::slice;
+use core::ffi::c_void;
+use core::marker::PhantomData;
+use core::mem;
+#[cfg(feature = "exts")]
+use crate::alloc_api::{string::String, vec, vec::Vec};
+
+#[cfg(feature = "exts")]
+mod load_option;
+#[cfg(feature = "exts")]
+pub use self::load_option::{
+    FilePaths, LoadOption, LoadOptionAttributes, LoadOptionCategory, LoadOptionError,
+};
+
+#[cfg(feature = "exts")]
+pub mod secure_boot;
+
+mod os_indications;
+pub use self::os_indications::OsIndications;
+
+mod reset;
+pub use self::reset::{ResetData, ResetDataError};
+
+mod typed_variable;
+pub use self::typed_variable::{PlainData, UefiVariable};
+
+mod variable_names;
+pub use self::variable_names::VariableNames;
+#[cfg(feature = "exts")]
+pub use self::variable_names::{VariableKey, VariableKeys};
+
+mod time;
+pub use self::time::TimeError;
+
+/// Contains pointers to all of the runtime services.
+///
+/// This table, and the function pointers it contains are valid
+/// even after the UEFI OS loader and OS have taken control of the platform.
+#[repr(C)]
+pub struct RuntimeServices {
+    pub(super) header: Header,
+    get_time:
+        unsafe extern "efiapi" fn(time: *mut Time, capabilities: *mut TimeCapabilities) -> Status,
+    set_time: unsafe extern "efiapi" fn(time: &Time) -> Status,
+    get_wakeup_time: unsafe extern "efiapi" fn(
+        enabled: &mut bool,
+        pending: &mut bool,
+        time: *mut Time,
+    ) -> Status,
+    set_wakeup_time: unsafe extern "efiapi" fn(enable: bool, time: *const Time) -> Status,
+    set_virtual_address_map: unsafe extern "efiapi" fn(
+        map_size: usize,
+        desc_size: usize,
+        desc_version: u32,
+        virtual_map: *mut MemoryDescriptor,
+    ) -> Status,
+    convert_pointer: extern "efiapi" fn(
+        debug_disposition: usize,
+        address: &mut *const u8
+    ) -> Status,
+    get_variable: extern "efiapi" fn(
+        variable_name: *const Char16,
+        vendor_guid: &Guid,
+        attributes: *mut u32,
+        data_size: &mut usize,
+        data: *mut c_void
+    ) -> Status,
+    get_next_variable_name: extern "efiapi" fn(
+        variable_name_size: &mut usize,
+        variable_name: *mut Char16,
+        vendor_guid: &mut Guid
+    ) -> Status,
+    set_variable: extern "efiapi" fn(
+        variable_name: *const Char16,
+        vendor_guid: &Guid,
+        attributes: u32,
+        data_size: usize,
+        data: *const c_void
+    ) -> Status,
+    get_next_high_monotonic_count: extern "efiapi" fn(high_count: &mut u32) -> Status,
+    reset: unsafe extern "efiapi" fn(
+        rt: ResetType,
+        status: Status,
+        data_size: usize,
+        data: *const u8,
+    ) -> !,
+
+    // UEFI 2.0 Capsule Services.
+    update_capsule: extern "efiapi" fn(
+        capsule_header_array: *const &CapsuleHeader,
+        capsule_count: usize,
+        scatter_gather_list: u64,
+    ) -> Status,
+    query_capsule_capabilities: extern "efiapi" fn(
+        capsule_header_array: *const &CapsuleHeader,
+        capsule_count: usize,
+        maximum_capsule_size: &mut u64,
+        reset_type: &mut u32,
+    ) -> Status,
+
+    // Miscellaneous UEFI 2.0 Service.
+    query_variable_info: extern "efiapi" fn(
+        attributes: u32,
+        maximum_variable_storage_size: &mut u64,
+        remaining_variable_storage_size: &mut u64,
+        maximum_variable_size: &mut u64,
+    ) -> Status,
+}
+
+impl RuntimeServices {
+    /// Query the current time and date information
+    pub fn get_time(&self) -> Result<Time> {
+        let mut time = MaybeUninit::<Time>::uninit();
+        unsafe { (self.get_time)(time.as_mut_ptr(), ptr::null_mut()) }
+            .into_with_val(|| unsafe { time.assume_init() })
+    }
+
+    /// Query the current time and date information and the RTC capabilities
+    pub fn get_time_and_caps(&self) -> Result<(Time, TimeCapabilities)> {
+        let mut time = MaybeUninit::<Time>::uninit();
+        let mut caps = MaybeUninit::<TimeCapabilities>::uninit();
+        unsafe { (self.get_time)(time.as_mut_ptr(), caps.as_mut_ptr()) }
+            .into_with_val(|| unsafe { (time.assume_init(), caps.assume_init()) })
+    }
+
+    /// Sets the current local time and date information
+    ///
+    /// During runtime, if a PC-AT CMOS device is present in the platform, the
+    /// caller must synchronize access to the device before calling `set_time`.
+    ///
+    /// # Safety
+    ///
+    /// Undefined behavior could happen if multiple tasks try to
+    /// use this function at the same time without synchronisation.
+    pub unsafe fn set_time(&mut self, time: &Time) -> Result {
+        (self.set_time)(time).into()
+    }
+
+    /// Query the current wakeup alarm clock setting
+    ///
+    /// Returns whether the alarm is enabled, whether it is pending (i.e. it
+    /// has been signaled), and the time at which it is set to go off.
+    ///
+    /// # Errors
+    ///
+    /// - `Unsupported` if the platform does not support a wakeup alarm
+    /// - `DeviceError` if the time could not be retrieved from the hardware
+    pub fn get_wakeup_time(&self) -> Result<(bool, bool, Time)> {
+        let mut enabled = false;
+        let mut pending = false;
+        let mut time = MaybeUninit::<Time>::uninit();
+        unsafe { (self.get_wakeup_time)(&mut enabled, &mut pending, time.as_mut_ptr()) }
+            .into_with_val(|| (enabled, pending, unsafe { time.assume_init() }))
+    }
+
+    /// Sets the system wakeup alarm clock time
+    ///
+    /// The platform will wake up or power on at the specified time, for
+    /// example after having been shut down with `reset(ResetType::Shutdown)`.
+    ///
+    /// # Safety
+    ///
+    /// Undefined behavior could happen if multiple tasks try to
+    /// use this function at the same time without synchronisation.
+    pub unsafe fn set_wakeup_time(&self, time: &Time) -> Result {
+        (self.set_wakeup_time)(true, time).into()
+    }
+
+    /// Disables the system wakeup alarm clock
+    ///
+    /// # Safety
+    ///
+    /// Undefined behavior could happen if multiple tasks try to
+    /// use this function at the same time without synchronisation.
+    pub unsafe fn disable_wakeup_time(&self) -> Result {
+        (self.set_wakeup_time)(false, ptr::null()).into()
+    }
+
+    /// Changes the runtime addressing mode of EFI firmware from physical to virtual.
+    ///
+    /// # Safety
+    ///
+    /// Setting new virtual memory map is unsafe and may cause undefined behaviors.
+    pub unsafe fn set_virtual_address_map(&self, map: &mut [MemoryDescriptor]) -> Result {
+        // Unsafe Code Guidelines guarantees that there is no padding in an array or a slice
+        // between its elements if the element type is `repr(C)`, which is our case.
+        //
+        // See https://rust-lang.github.io/unsafe-code-guidelines/layout/arrays-and-slices.html
+        let map_size = core::mem::size_of_val(map);
+        let entry_size = core::mem::size_of::<MemoryDescriptor>();
+        let entry_version = crate::table::boot::MEMORY_DESCRIPTOR_VERSION;
+        let map_ptr = map.as_mut_ptr();
+        (self.set_virtual_address_map)(map_size, entry_size, entry_version, map_ptr).into()
+    }
+
+    /// Converts a physical pointer to the virtual address it is mapped to by
+    /// `set_virtual_address_map`.
+    ///
+    /// A null pointer is returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// - `NotFound` if the pointer is not part of the current memory map
+    ///
+    /// # Safety
+    ///
+    /// This may only be called from the notification function of an event
+    /// of type `EventType::SIGNAL_VIRTUAL_ADDRESS_CHANGE`, while
+    /// `set_virtual_address_map` is in progress.
+    pub unsafe fn convert_pointer<T>(&self, address: *const T) -> Result<*const T> {
+        // Tells the firmware that the pointer may be null
+        const OPTIONAL_PTR: usize = 0x1;
+
+        let mut address = address as *const u8;
+        (self.convert_pointer)(OPTIONAL_PTR, &mut address).into_with_val(|| address as *const T)
+    }
+
+    /// Returns an iterator over EFI variables.
+    ///
+    /// This iterator stops at the first firmware error, or at the first name
+    /// which is not valid UCS-2: prefer `variable_keys`.
+    #[cfg(feature = "exts")]
+    pub fn variables<'a>(&'a self) -> VariablesIterator {
+        VariablesIterator::new(self)
+    }
+
+    /// Enumerates the names and vendor GUIDs of EFI variables, using
+    /// `buffer` to store each name.
+    pub fn variable_names<'buf>(&self, buffer: &'buf mut [u16]) -> VariableNames<'_, 'buf> {
+        VariableNames::new(self, buffer)
+    }
+
+    /// Returns a fallible iterator over the names and vendor GUIDs of EFI
+    /// variables.
+    #[cfg(feature = "exts")]
+    pub fn variable_keys(&self) -> VariableKeys<'_> {
+        VariableKeys::new(self)
+    }
+
+    /// Used internally by the variable name iterators.
+    ///
+    /// The returned size is a number of characters.
+    fn get_next_variable_name(&self, variable_name: &mut [u16], vendor_guid: &mut Guid) -> (Status, usize) {
+        let mut buf_size = variable_name.len() * mem::size_of::<u16>();
+        let status = (self.get_next_variable_name)(
+            &mut buf_size,
+            variable_name as *mut _ as *mut Char16,
+            vendor_guid,
+        );
+
+        (status, (buf_size + 1) / mem::size_of::<u16>())
+    }
+
+    /// Get the data stored with the variable.
+    #[cfg(feature = "exts")]
+    pub fn get_variable(&self, variable_name: &str, vendor_guid: &Guid) -> Result<(Vec<u8>, VariableAttributes)> {
+        let mut attributes = 0;
+        let name_len = variable_name.chars().count();
+        let mut name = vec![0; name_len + 1];
+
+        ucs2::encode(variable_name, &mut name[..name_len])
+            .map_err(|_| Status::INVALID_PARAMETER)?;
+
+        // use some default size to try to avoid a second call because of buffer size
+        let mut data = vec![0; 64];
+        let mut data_size = data.len();
+
+        // this might be called when multiple processors are running, so the data could get bigger
+        // even after resizing the buffer, so this should be called in a loop
+        loop {
+            match (self.get_variable)(name.as_ptr() as *const Char16, vendor_guid, 
+                &mut attributes, &mut data_size, data.as_mut_ptr() as *mut c_void) {
+                Status::SUCCESS => {
+                    // resize the vector to reflect the size of the data returned
+                    data.truncate(data_size);
+                    return Ok(crate::Completion::new(Status::SUCCESS,
+                            (data, VariableAttributes::from_bits_truncate(attributes))));
+                },
+                Status::BUFFER_TOO_SMALL => {
+                    // resize the buffer 
+                    data.resize(data_size, 0);
+                },
+                s => return Err(s.into()),
+            }
+        }
+    }
+
+    /// Sets the value of a variable. This service can be used to create a new variable, 
+    /// modify the value of an existing variable, or to delete an existing variable.
+    ///
+    /// The name is converted to UCS-2 in an allocated buffer: use
+    /// `set_variable_cstr16` to set a variable without allocating.
+    #[cfg(feature = "exts")]
+    pub fn set_variable(
+        &self,
+        variable_name: &str,
+        vendor_guid: &Guid,
+        attributes: VariableAttributes,
+        data: &[u8]
+    ) -> Result {
+        let name_len = variable_name.chars().count();
+        let mut name = vec![0; name_len + 1];
+
+        ucs2::encode(variable_name, &mut name[..name_len])
+            .map_err(|_| Status::INVALID_PARAMETER)?;
+
+        // `encode` only writes valid UCS-2 characters, and the buffer ends with a NUL.
+        let name = unsafe { CStr16::from_u16_with_nul_unchecked(&name) };
+        self.set_variable_cstr16(name, vendor_guid, attributes, data)
+    }
+
+    /// Sets the value of a variable whose name is already a UCS-2 string.
+    ///
+    /// This behaves like `set_variable`, but does not need to allocate.
+    pub fn set_variable_cstr16(
+        &self,
+        variable_name: &CStr16,
+        vendor_guid: &Guid,
+        attributes: VariableAttributes,
+        data: &[u8],
+    ) -> Result {
+        (self.set_variable)(
+            variable_name.as_ptr(),
+            vendor_guid,
+            attributes.bits(),
+            data.len(),
+            data.as_ptr() as *const c_void,
+        )
+        .into()
+    }
+
+    /// Sets the value of a variable, after checking that it fits in the
+    /// variable storage.
+    ///
+    /// This behaves like `set_variable`, but first queries the storage
+    /// available for variables with the given attributes. If the name and
+    /// data cannot fit, the firmware is not called and a `BadBufferSize`
+    /// error is returned, carrying the storage information that was used to
+    /// reject the write. Other errors carry no data.
+    ///
+    /// Deleting a variable is never rejected, and for `APPEND_WRITE` only the
+    /// remaining storage is checked, since the size of the existing data is
+    /// not known.
+    #[cfg(feature = "exts")]
+    pub fn set_variable_checked(
+        &self,
+        variable_name: &str,
+        vendor_guid: &Guid,
+        attributes: VariableAttributes,
+        data: &[u8],
+    ) -> Result<(), Option<VariableStorageInfo>> {
+        let append = attributes.contains(VariableAttributes::APPEND_WRITE);
+        if !data.is_empty() || append {
+            let info = self
+                .query_variable_info(attributes - VariableAttributes::APPEND_WRITE)
+                .map_err(|err| Error::new(err.status(), None))?
+                .log();
+
+            // Names are stored as NUL-terminated UCS-2 strings.
+            let name_size = (variable_name.chars().count() as u64 + 1) * 2;
+            let size = name_size + data.len() as u64;
+            if (!append && size > info.maximum_variable_size)
+                || size > info.remaining_variable_storage_size
+            {
+                return Err(Error::new(Status::BAD_BUFFER_SIZE, Some(info)));
+            }
+        }
+
+        self.set_variable(variable_name, vendor_guid, attributes, data)
+            .map_err(|err| Error::new(err.status(), None))
+    }
+
+    /// Returns information about the storage available for variables with
+    /// the given attributes.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidParameter` if the attribute combination is invalid
+    /// - `Unsupported` if the attribute combination is not supported by the
+    ///   platform, or if the firmware predates UEFI 2.0
+    pub fn query_variable_info(
+        &self,
+        attributes: VariableAttributes,
+    ) -> Result<VariableStorageInfo> {
+        if self.header.revision < Revision::new(2, 0) {
+            return Err(Status::UNSUPPORTED.into());
+        }
+
+        let mut info = VariableStorageInfo {
+            maximum_variable_storage_size: 0,
+            remaining_variable_storage_size: 0,
+            maximum_variable_size: 0,
+        };
+        (self.query_variable_info)(
+            attributes.bits(),
+            &mut info.maximum_variable_storage_size,
+            &mut info.remaining_variable_storage_size,
+            &mut info.maximum_variable_size,
+        )
+        .into_with_val(|| info)
+    }
+
+    /// Increments the platform's high monotonic count, and returns its new
+    /// value.
+    ///
+    /// This is the upper half of the count returned by
+    /// `BootServices::get_next_monotonic_count`. It persists across resets,
+    /// and is the only part of the monotonic counter that can be used after
+    /// exiting boot services.
+    ///
+    /// # Errors
+    ///
+    /// - `DeviceError` if the counter overflowed or the device failed
+    pub fn get_next_high_monotonic_count(&self) -> Result<u32> {
+        let mut high_count = 0;
+        (self.get_next_high_monotonic_count)(&mut high_count).into_with_val(|| high_count)
+    }
+
+    /// Passes capsules to the firmware.
+    ///
+    /// Capsules flagged with `CapsuleFlags::PERSIST_ACROSS_RESET` are
+    /// processed after the next reset, and need a `ScatterGatherList`
+    /// describing where they are located in memory. The other capsules are
+    /// processed immediately, and the scatter-gather list is ignored.
+    ///
+    /// If a capsule is flagged with `CapsuleFlags::INITIATE_RESET`, this
+    /// function does not return on success.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidParameter` if no capsules were given, or if the flags are
+    ///   invalid
+    /// - `DeviceError` if the capsule update was started but failed
+    /// - `Unsupported` if a capsule type is not supported by the platform, or
+    ///   if the firmware predates UEFI 2.0
+    /// - `OutOfResources` if there is not enough memory to process the
+    ///   capsules
+    pub fn update_capsule(
+        &self,
+        capsules: &[&CapsuleHeader],
+        scatter_gather_list: Option<&ScatterGatherList<'_>>,
+    ) -> Result {
+        if self.header.revision < Revision::new(2, 0) {
+            return Err(Status::UNSUPPORTED.into());
+        }
+
+        let scatter_gather_list = scatter_gather_list.map_or(0, |list| list.address);
+        (self.update_capsule)(capsules.as_ptr(), capsules.len(), scatter_gather_list).into()
+    }
+
+    /// Checks whether the given capsules can be passed to `update_capsule`.
+    ///
+    /// Returns the maximum size of a capsule that can be passed to the
+    /// firmware together with these capsules, and the type of reset that
+    /// processing them requires.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidParameter` if no capsules were given
+    /// - `Unsupported` if a capsule type is not supported by the platform, or
+    ///   if the firmware predates UEFI 2.0
+    /// - `OutOfResources` if the capsules can only be processed across a
+    ///   reset, and the platform cannot do so
+    pub fn query_capsule_capabilities(
+        &self,
+        capsules: &[&CapsuleHeader],
+    ) -> Result<(u64, ResetType)> {
+        if self.header.revision < Revision::new(2, 0) {
+            return Err(Status::UNSUPPORTED.into());
+        }
+
+        let mut maximum_capsule_size = 0;
+        let mut reset_type = 0;
+        let completion = (self.query_capsule_capabilities)(
+            capsules.as_ptr(),
+            capsules.len(),
+            &mut maximum_capsule_size,
+            &mut reset_type,
+        )
+        .into_with_val(|| maximum_capsule_size)?;
+
+        // The reset type is produced by the firmware, so it must be checked
+        // before it can be turned into a `ResetType`.
+        match ResetType::from_raw(reset_type) {
+            Some(reset_type) => Ok(completion.map(|size| (size, reset_type))),
+            None => Err(Status::DEVICE_ERROR.into()),
+        }
+    }
+
+    /// Resets the computer.
+    ///
+    /// The data, if any, should start with a NUL-terminated UCS-2 string,
+    /// which is followed by a GUID for `ResetType::PlatformSpecific` resets.
+    /// It is passed to the firmware as is: use `reset_with_data` to pass data
+    /// which is known to follow this layout.
+    pub fn reset(&self, rt: ResetType, status: Status, data: Option<&[u8]>) -> ! {
+        let (size, data) = match data {
+            Some(data) => (data.len(), data.as_ptr()),
+            None => (0, ptr::null()),
+        };
+
+        unsafe { (self.reset)(rt, status, size, data) }
+    }
+
+    /// Resets the computer, passing the reason for the reset and optional
+    /// data to the firmware.
+    pub fn reset_with_data(&self, status: Status, data: &ResetData<'_>) -> ! {
+        let bytes = data.as_bytes();
+        unsafe { (self.reset)(data.reset_type(), status, bytes.len(), bytes.as_ptr()) }
+    }
+}
+
+impl super::Table for RuntimeServices {
+    const SIGNATURE: u64 = 0x5652_4553_544e_5552;
+}
+
+bitflags! {
+    /// EFI Variable Attributes
+    pub struct VariableAttributes: u32 {
+        /// Variable's value persists across resets and power cycles.
+        const NON_VOLATILE = 1 << 0;
+
+        /// Variable can be accessed during boot time.
+        const BOOTSERVICE_ACCESS = 1 << 1;
+
+        /// Variable can be accessed during run time.
+        const RUNTIME_ACCESS = 1 << 2;
+
+        /// Variable contains Hardware Error Record data.
+        const HARDWARE_ERROR_RECORD = 1 << 3;
+
+        /// AUTHENTICATED_WRITE_ACCESS is deprecated and should be considered reserved.
+        const AUTHENTICATED_WRITE_ACCESS = 1 << 4;
+
+        /// When the attribute TIME_BASED_AUTHENTICATED_WRITE_ACCESS is set, then the Data buffer shall begin with 
+        /// an instance of a complete (and serialized) EFI_VARIABLE_AUTHENTICATION_2 descriptor.
+        const TIME_BASED_AUTHENTICATED_WRITE_ACCESS = 1 << 5;
+
+        /// SetVariable data is appended to the present data.
+        const APPEND_WRITE = 1 << 6;
+
+        /// This attribute indicates that the variable payload begins with an EFI_VARIABLE_AUTHENTICATION_3 structure, 
+        /// and potentially more structures as indicated by fields of this structure.
+        const ENHANCED_AUTHENTICATED_ACCESS = 1 << 7;
+    }
+}
+
+/// Storage information for variables of a given attribute set, as returned
+/// by `RuntimeServices::query_variable_info`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct VariableStorageInfo {
+    /// Maximum size of the storage space available for the variables.
+    pub maximum_variable_storage_size: u64,
+
+    /// Remaining size of the storage space available for the variables.
+    pub remaining_variable_storage_size: u64,
+
+    /// Maximum size of an individual variable, including its name.
+    pub maximum_variable_size: u64,
+}
+
+/// An EFI Variable
+#[cfg(feature = "exts")]
+#[derive(Debug, Clone)]
+pub struct Variable {
+    /// The variable name.
+    pub name: String,
+
+    /// The vendor's guid.
+    pub vendor: Guid,
+}
+
+#[cfg(feature = "exts")]
+impl Variable {
+    /// Vendor GUID of all architectually defined variables.
+    pub const GLOBAL_VARIABLE_GUID: Guid = Guid::from_values(
+        0x8BE4DF61, 0x93CA, 0x11d2, 0xAA0D, [0x00, 0xE0, 0x98, 0x03, 0x2B, 0x8C]);
+    /// Creates an EFI Variable from a variable name and the vendor guid.
+    pub fn new(name: String, vendor: Guid) -> Variable {
+        Variable {
+            name,
+            vendor
+        }
+    }
+}
+
+/// An iterator for EFI variables.
+#[cfg(feature = "exts")]
+pub struct VariablesIterator<'a> {
+    rt: &'a RuntimeServices,
+    buffer: Vec<u16>,
+    guid: Guid,
+}
+
+#[cfg(feature = "exts")]
+impl<'a> VariablesIterator<'a> {
+    /// Creates an iterator over EFI Variables.
+    pub fn new(runtime_services: &'a RuntimeServices) -> VariablesIterator {
+        VariablesIterator {
+            rt: runtime_services,
+            buffer: vec![0; 32], // try to avoid BUFFER_TOO_SMALL
+            guid: Guid::from_values(0, 0, 0, 0, [0, 0, 0, 0, 0, 0]),
+        }
+    }
+}
+
+#[cfg(feature = "exts")]
+impl<'a> Iterator for VariablesIterator<'a> {
+    type Item = Variable;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.rt.get_next_variable_name(self.buffer.as_mut_slice(), &mut self.guid) {
+            (Status::SUCCESS, _) => (),
+            (Status::BUFFER_TOO_SMALL, len) => {
+                // Note that if EFI_BUFFER_TOO_SMALL is returned,
+                // the VariableName buffer was too small for the next variable. 
+                // When such an error occurs, the VariableNameSize is updated 
+                // to reflect the size of buffer needed.
+                self.buffer.resize(len, 0);
+
+                let (status, _) = self.rt.get_next_variable_name(self.buffer.as_mut_slice(), &mut self.guid);
+                if status.is_error() {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+
+        let name_len = self.buffer.as_slice().iter().take_while(|&&e| e != 0).count();
+
+        let mut utf8_buffer = vec![0; name_len * 3];
+
+        // the firmware may return names which are not valid ucs2
+        let name_len = ucs2::decode(&self.buffer[..name_len], &mut utf8_buffer[..]).ok()?;
+
+        // truncate the vector so that we can give it to the String constructor
+        utf8_buffer.truncate(name_len);
+
+        // ucs2::decode only returns valid utf8 encodings
+        let name = unsafe {
+            String::from_utf8_unchecked(utf8_buffer)
+        };
+
+        return Some(Variable::new(name, self.guid));
+    }
+}
+
+/// The current time information
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct Time {
+    year: u16,  // 1900 - 9999
+    month: u8,  // 1 - 12
+    day: u8,    // 1 - 31
+    hour: u8,   // 0 - 23
+    minute: u8, // 0 - 59
+    second: u8, // 0 - 59
+    _pad1: u8,
+    nanosecond: u32, // 0 - 999_999_999
+    time_zone: i16,  // -1440 to 1440, or 2047 if unspecified
+    daylight: Daylight,
+    _pad2: u8,
+}
+
+bitflags! {
+    /// Flags describing the capabilities of a memory range.
+    pub struct Daylight: u8 {
+        /// Time is affected by daylight savings time
+        const ADJUST_DAYLIGHT = 0x01;
+        /// Time has been adjusted for daylight savings time
+        const IN_DAYLIGHT = 0x02;
+    }
+}
+
+impl Time {
+    /// Build an UEFI time struct
+    ///
+    /// Returns an error if a field is out of range, or if the day does not
+    /// exist in the given month.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        nanosecond: u32,
+        time_zone: i16,
+        daylight: Daylight,
+    ) -> core::result::Result<Self, TimeError> {
+        let time = Self {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            _pad1: 0,
+            nanosecond,
+            time_zone,
+            daylight,
+            _pad2: 0,
+        };
+        time.validate().map(|_| time)
+    }
+
+    /// Query the year
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Query the month
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Query the day
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Query the hour
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// Query the minute
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// Query the second
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// Query the nanosecond
+    pub fn nanosecond(&self) -> u32 {
+        self.nanosecond
+    }
+
+    /// Query the time offset in minutes from UTC, or None if using local time
+    pub fn time_zone(&self) -> Option<i16> {
+        if self.time_zone == 2047 {
+            None
+        } else {
+            Some(self.time_zone)
+        }
+    }
+
+    /// Query the daylight savings time information
+    pub fn daylight(&self) -> Daylight {
+        self.daylight
+    }
+}
+
+impl fmt::Debug for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}-{} ", self.year, self.month, self.day)?;
+        write!(
+            f,
+            "{}:{}:{}.{} ",
+            self.hour, self.minute, self.second, self.nanosecond
+        )?;
+        write!(f, "{} {:?}", self.time_zone, self.daylight)
+    }
+}
+
+/// Real time clock capabilities
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(C)]
+pub struct TimeCapabilities {
+    /// Reporting resolution of the clock in counts per second. 1 for a normal
+    /// PC-AT CMOS RTC device, which reports the time with 1-second resolution.
+    pub resolution: u32,
+
+    /// Timekeeping accuracy in units of 1e-6 parts per million.
+    pub accuracy: u32,
+
+    /// Whether a time set operation clears the device's time below the
+    /// "resolution" reporting level. False for normal PC-AT CMOS RTC devices.
+    pub sets_to_zero: bool,
+}
+
+/// Number of pages needed to hold `size` bytes
+fn pages_for(size: usize) -> usize {
+    (size + PAGE_SIZE - 1) / PAGE_SIZE
+}
+
+bitflags! {
+    /// Flags describing how a capsule is processed by the firmware.
+    ///
+    /// The lower 16 bits are defined by the capsule's GUID, and can be set
+    /// with `CapsuleBuilder::guid_flags`.
+    pub struct CapsuleFlags: u32 {
+        /// The capsule is processed after the next reset. It must be passed
+        /// to `update_capsule` together with a `ScatterGatherList`.
+        const PERSIST_ACROSS_RESET = 0x0001_0000;
+        /// The capsule is installed in the configuration table after the
+        /// next reset. Requires `PERSIST_ACROSS_RESET`.
+        const POPULATE_SYSTEM_TABLE = 0x0002_0000;
+        /// The firmware resets the system after accepting the capsule.
+        /// Requires `PERSIST_ACROSS_RESET`.
+        const INITIATE_RESET = 0x0004_0000;
+    }
+}
+
+/// Header of a capsule, which is followed in memory by the capsule's body.
+///
+/// References to a capsule header can only be obtained from a `Capsule`,
+/// which guarantees that the header is followed by the whole capsule image.
+#[derive(Debug)]
+#[repr(C)]
+pub struct CapsuleHeader {
+    capsule_guid: Guid,
+    header_size: u32,
+    flags: u32,
+    capsule_image_size: u32,
+}
+
+impl CapsuleHeader {
+    /// GUID identifying the capsule's format
+    pub fn capsule_guid(&self) -> Guid {
+        self.capsule_guid
+    }
+
+    /// Size of the header, which may be larger than this structure
+    pub fn header_size(&self) -> u32 {
+        self.header_size
+    }
+
+    /// Flags defined by the specification
+    pub fn flags(&self) -> CapsuleFlags {
+        CapsuleFlags::from_bits_truncate(self.flags)
+    }
+
+    /// Flags defined by the capsule's GUID
+    pub fn guid_flags(&self) -> u16 {
+        self.flags as u16
+    }
+
+    /// Size of the whole capsule, including its header
+    pub fn capsule_image_size(&self) -> u32 {
+        self.capsule_image_size
+    }
+}
+
+/// Builds a `Capsule` from a GUID, flags and a body.
+#[derive(Debug)]
+pub struct CapsuleBuilder {
+    capsule_guid: Guid,
+    header_size: u32,
+    flags: CapsuleFlags,
+    guid_flags: u16,
+}
+
+impl CapsuleBuilder {
+    /// Starts building a capsule of the format identified by `capsule_guid`
+    pub fn new(capsule_guid: Guid) -> Self {
+        CapsuleBuilder {
+            capsule_guid,
+            header_size: mem::size_of::<CapsuleHeader>() as u32,
+            flags: CapsuleFlags::empty(),
+            guid_flags: 0,
+        }
+    }
+
+    /// Sets the flags defined by the specification
+    pub fn flags(mut self, flags: CapsuleFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the flags defined by the capsule's GUID
+    pub fn guid_flags(mut self, guid_flags: u16) -> Self {
+        self.guid_flags = guid_flags;
+        self
+    }
+
+    /// Sets the size of the header, for formats which extend it
+    ///
+    /// The space between the end of the `CapsuleHeader` and the body is
+    /// zero-filled. The size defaults to the size of `CapsuleHeader`, and
+    /// cannot be smaller than that.
+    pub fn header_size(mut self, header_size: u32) -> Self {
+        assert!(header_size as usize >= mem::size_of::<CapsuleHeader>());
+        self.header_size = header_size;
+        self
+    }
+
+    /// Allocates pages for the capsule, and copies the header and `body`
+    /// into them.
+    ///
+    /// # Errors
+    ///
+    /// - `BadBufferSize` if the capsule is larger than 4 GiB
+    /// - `OutOfResources` if the pages could not be allocated
+    pub fn build<'boot>(self, bs: &'boot BootServices, body: &[u8]) -> Result<Capsule<'boot>> {
+        let size = self.header_size as usize + body.len();
+        if size > u32::MAX as usize {
+            return Err(Status::BAD_BUFFER_SIZE.into());
+        }
+
+        let pages = pages_for(size);
+        bs.allocate_pages(AllocateType::AnyPages, MemoryType::LOADER_DATA, pages)
+            .map_inner(|address| {
+                let header = CapsuleHeader {
+                    capsule_guid: self.capsule_guid,
+                    header_size: self.header_size,
+                    flags: self.flags.bits() | u32::from(self.guid_flags),
+                    capsule_image_size: size as u32,
+                };
+                let buffer = address as usize as *mut u8;
+                unsafe {
+                    ptr::write_bytes(buffer, 0, self.header_size as usize);
+                    ptr::write(buffer as *mut CapsuleHeader, header);
+                    ptr::copy_nonoverlapping(
+                        body.as_ptr(),
+                        buffer.add(self.header_size as usize),
+                        body.len(),
+                    );
+                }
+                Capsule {
+                    boot_services: bs,
+                    address,
+                    pages,
+                }
+            })
+    }
+}
+
+/// A capsule in pages allocated from boot services
+///
+/// The pages are freed when this is dropped.
+pub struct Capsule<'boot> {
+    boot_services: &'boot BootServices,
+    address: u64,
+    pages: usize,
+}
+
+impl Capsule<'_> {
+    /// The capsule's header, which can be passed to `update_capsule`
+    pub fn header(&self) -> &CapsuleHeader {
+        unsafe { &*(self.address as usize as *const CapsuleHeader) }
+    }
+
+    /// The whole capsule, including its header
+    pub fn as_bytes(&self) -> &[u8] {
+        let size = self.header().capsule_image_size as usize;
+        unsafe { slice::from_raw_parts(self.address as usize as *const u8, size) }
+    }
+}
+
+impl fmt::Debug for Capsule<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Capsule")
+            .field("address", &self.address)
+            .field("header", self.header())
+            .finish()
+    }
+}
+
+impl Drop for Capsule<'_> {
+    fn drop(&mut self) {
+        let _ = self.boot_services.free_pages(self.address, self.pages);
+    }
+}
+
+/// Entry of a scatter-gather list
+///
+/// An entry with a zero length and a zero address terminates the list, and
+/// an entry with a zero length and a non-zero address continues the list at
+/// that address.
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+struct CapsuleBlockDescriptor {
+    length: u64,
+    address: u64,
+}
+
+/// Scatter-gather list describing the location of capsules in memory
+///
+/// This is required by `update_capsule` for capsules which persist across a
+/// reset. The list is stored in pages allocated from boot services, which
+/// are freed when this is dropped.
+pub struct ScatterGatherList<'boot> {
+    boot_services: &'boot BootServices,
+    address: u64,
+    pages: usize,
+    _capsules: PhantomData<&'boot CapsuleHeader>,
+}
+
+impl<'boot> ScatterGatherList<'boot> {
+    /// Builds a scatter-gather list with one data block per capsule
+    ///
+    /// # Errors
+    ///
+    /// - `OutOfResources` if the pages could not be allocated
+    pub fn new(bs: &'boot BootServices, capsules: &[&'boot CapsuleHeader]) -> Result<Self> {
+        let count = capsules.len() + 1;
+        let pages = pages_for(count * mem::size_of::<CapsuleBlockDescriptor>());
+        bs.allocate_pages(AllocateType::AnyPages, MemoryType::LOADER_DATA, pages)
+            .map_inner(|address| {
+                let descriptors = address as usize as *mut CapsuleBlockDescriptor;
+                let blocks = capsules.iter().map(|header| CapsuleBlockDescriptor {
+                    length: u64::from(header.capsule_image_size),
+                    address: *header as *const CapsuleHeader as usize as u64,
+                });
+                let terminator = CapsuleBlockDescriptor {
+                    length: 0,
+                    address: 0,
+                };
+                for (i, block) in blocks.chain(Some(terminator)).enumerate() {
+                    unsafe { descriptors.add(i).write(block) };
+                }
+                ScatterGatherList {
+                    boot_services: bs,
+                    address,
+                    pages,
+                    _capsules: PhantomData,
+                }
+            })
+    }
+
+    /// Physical address of the first entry of the list
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+}
+
+impl Drop for ScatterGatherList<'_> {
+    fn drop(&mut self) {
+        let _ = self.boot_services.free_pages(self.address, self.pages);
+    }
+}
+
+/// The type of system reset.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ResetType {
+    /// Resets all the internal circuitry to its initial state.
+    ///
+    /// This is analogous to power cycling the device.
+    Cold = 0,
+    /// The processor is reset to its initial state.
+    Warm,
+    /// The components are powered off.
+    Shutdown,
+    /// A platform-specific reset type.
+    ///
+    /// The additional data must be a pointer to
+    /// a null-terminated string followed by an UUID.
+    PlatformSpecific,
+    // SAFETY: This enum is only fed as input to the firmware. The firmware
+    //         only returns it from `query_capsule_capabilities`, where the
+    //         raw value is checked with `ResetType::from_raw`. Therefore,
+    //         unexpected values can never come from the firmware, and modeling
+    //         this as a Rust enum seems safe.
+}
+
+impl ResetType {
+    /// Checks a reset type produced by the firmware
+    fn from_raw(raw: u32) -> Option<Self> {
+        match raw {
+            0 => Some(ResetType::Cold),
+            1 => Some(ResetType::Warm),
+            2 => Some(ResetType::Shutdown),
+            3 => Some(ResetType::PlatformSpecific),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Features which the OS can ask the firmware to use on the next boot.
+
+use bitflags::bitflags;
+
+bitflags! {
+    /// Flags of the `OsIndicationsSupported` and `OsIndications` variables
+    ///
+    /// The firmware lists the features it supports in
+    /// `OsIndicationsSupported`, and the OS requests some of them for the
+    /// next boot by setting `OsIndications`.
+    pub struct OsIndications: u64 {
+        /// Stop in the firmware's user interface on the next boot.
+        const BOOT_TO_FW_UI = 0x0000_0000_0000_0001;
+        /// The firmware supports timestamp-based revocation in `dbt`.
+        const TIMESTAMP_REVOCATION = 0x0000_0000_0000_0002;
+        /// Process the capsules stored on the EFI system partition, in
+        /// `\EFI\UpdateCapsule`, on the next boot.
+        const FILE_CAPSULE_DELIVERY_SUPPORTED = 0x0000_0000_0000_0004;
+        /// The firmware supports firmware management protocol capsules.
+        const FMP_CAPSULE_SUPPORTED = 0x0000_0000_0000_0008;
+        /// The firmware reports capsule processing results in `Capsule####`
+        /// variables.
+        const CAPSULE_RESULT_VAR_SUPPORTED = 0x0000_0000_0000_0010;
+        /// Attempt OS-defined recovery on the next boot.
+        const START_OS_RECOVERY = 0x0000_0000_0000_0020;
+        /// Attempt platform-defined recovery on the next boot.
+        const START_PLATFORM_RECOVERY = 0x0000_0000_0000_0040;
+        /// Refresh the JSON configuration data on the next boot.
+        const JSON_CONFIG_DATA_REFRESH = 0x0000_0000_0000_0080;
+    }
+}
+
+#[cfg(feature = "exts")]
+mod variables {
+    use super::OsIndications;
+    use crate::table::runtime::{ResetType, RuntimeServices, Variable, VariableAttributes};
+    use crate::{Result, Status};
+    use core::convert::{Infallible, TryFrom};
+
+    /// Attributes of the `OsIndications` variable
+    const OS_INDICATIONS_ATTRIBUTES: VariableAttributes = VariableAttributes::from_bits_truncate(
+        VariableAttributes::NON_VOLATILE.bits()
+            | VariableAttributes::BOOTSERVICE_ACCESS.bits()
+            | VariableAttributes::RUNTIME_ACCESS.bits(),
+    );
+
+    impl RuntimeServices {
+        /// Reads a variable holding `OsIndications` flags
+        fn os_indications_variable(&self, name: &str) -> Result<OsIndications> {
+            let (data, _) = self
+                .get_variable(name, &Variable::GLOBAL_VARIABLE_GUID)?
+                .log();
+            let bytes =
+                <[u8; 8]>::try_from(data.as_slice()).map_err(|_| Status::COMPROMISED_DATA)?;
+            // Vendor and future bits must survive a read-modify-write.
+            Ok(unsafe { OsIndications::from_bits_unchecked(u64::from_le_bytes(bytes)) }.into())
+        }
+
+        /// Reads the `OsIndicationsSupported` variable, which lists the
+        /// features supported by the firmware
+        pub fn os_indications_supported(&self) -> Result<OsIndications> {
+            self.os_indications_variable("OsIndicationsSupported")
+        }
+
+        /// Reads the `OsIndications` variable, which lists the features
+        /// requested for the next boot
+        ///
+        /// Returns no flags if the variable is not set.
+        pub fn os_indications(&self) -> Result<OsIndications> {
+            match self.os_indications_variable("OsIndications") {
+                Err(err) if err.status() == Status::NOT_FOUND => Ok(OsIndications::empty().into()),
+                result => result,
+            }
+        }
+
+        /// Writes the `OsIndications` variable
+        pub fn set_os_indications(&self, indications: OsIndications) -> Result {
+            self.set_variable(
+                "OsIndications",
+                &Variable::GLOBAL_VARIABLE_GUID,
+                OS_INDICATIONS_ATTRIBUTES,
+                &indications.bits().to_le_bytes(),
+            )
+        }
+
+        /// Reboots into the firmware's user interface
+        ///
+        /// This does not return on success.
+        ///
+        /// # Errors
+        ///
+        /// - `Unsupported` if the firmware does not support
+        ///   `OsIndications::BOOT_TO_FW_UI`
+        pub fn reboot_to_firmware_setup(&self) -> Result<Infallible> {
+            let supported = match self.os_indications_supported() {
+                Ok(supported) => supported.log(),
+                Err(err) if err.status() == Status::NOT_FOUND => OsIndications::empty(),
+                Err(err) => return Err(err),
+            };
+            if !supported.contains(OsIndications::BOOT_TO_FW_UI) {
+                return Err(Status::UNSUPPORTED.into());
+            }
+
+            let indications = self.os_indications()?.log();
+            self.set_os_indications(indications | OsIndications::BOOT_TO_FW_UI)?
+                .log();
+            self.reset(ResetType::Cold, Status::SUCCESS, None)
+        }
+    }
+}
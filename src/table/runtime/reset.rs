@@ -0,0 +1,102 @@
+//! Data passed to the firmware when resetting the system.
+
+use super::ResetType;
+use crate::{CStr16, Guid};
+use core::mem;
+
+/// Errors which can occur when building `ResetData`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ResetDataError {
+    /// The provided buffer was too small to hold the reset data. You need at
+    /// least the indicated buffer size (in bytes).
+    InsufficientStorage(usize),
+    /// Platform-specific resets must be built with
+    /// `ResetData::platform_specific`, which takes the GUID of the reset.
+    PlatformSpecificNeedsGuid,
+}
+
+/// Reset data, which tells the firmware why and how the system is reset
+///
+/// The data starts with a NUL-terminated UCS-2 string describing the reason
+/// for the reset, which the firmware may record in its reset log. For
+/// `ResetType::PlatformSpecific` resets, the string is followed by a GUID
+/// identifying the kind of reset. An optional binary payload comes last.
+#[derive(Debug, Copy, Clone)]
+pub struct ResetData<'buf> {
+    reset_type: ResetType,
+    data: &'buf [u8],
+}
+
+impl<'buf> ResetData<'buf> {
+    /// Builds the data of a cold, warm or shutdown reset in `storage`
+    ///
+    /// Returns `ResetDataError::PlatformSpecificNeedsGuid` if `reset_type` is
+    /// `ResetType::PlatformSpecific`.
+    pub fn new(
+        storage: &'buf mut [u8],
+        reset_type: ResetType,
+        reason: &CStr16,
+        payload: &[u8],
+    ) -> Result<Self, ResetDataError> {
+        if reset_type == ResetType::PlatformSpecific {
+            return Err(ResetDataError::PlatformSpecificNeedsGuid);
+        }
+        Self::build(storage, reset_type, reason, &[], payload)
+    }
+
+    /// Builds the data of a platform-specific reset in `storage`
+    ///
+    /// The kind of reset is identified by `reset_guid`, which is defined by
+    /// the platform vendor.
+    pub fn platform_specific(
+        storage: &'buf mut [u8],
+        reason: &CStr16,
+        reset_guid: &Guid,
+        payload: &[u8],
+    ) -> Result<Self, ResetDataError> {
+        let reset_guid = reset_guid.to_bytes();
+        Self::build(
+            storage,
+            ResetType::PlatformSpecific,
+            reason,
+            &reset_guid,
+            payload,
+        )
+    }
+
+    fn build(
+        storage: &'buf mut [u8],
+        reset_type: ResetType,
+        reason: &CStr16,
+        reset_guid: &[u8],
+        payload: &[u8],
+    ) -> Result<Self, ResetDataError> {
+        let reason = reason.to_u16_slice_with_nul();
+        let reason_size = reason.len() * mem::size_of::<u16>();
+        let size = reason_size + reset_guid.len() + payload.len();
+        if storage.len() < size {
+            return Err(ResetDataError::InsufficientStorage(size));
+        }
+
+        for (bytes, &c) in storage.chunks_exact_mut(2).zip(reason) {
+            bytes.copy_from_slice(&c.to_le_bytes());
+        }
+        storage[reason_size..reason_size + reset_guid.len()].copy_from_slice(reset_guid);
+        storage[reason_size + reset_guid.len()..size].copy_from_slice(payload);
+
+        Ok(ResetData {
+            reset_type,
+            data: &storage[..size],
+        })
+    }
+
+    /// The type of reset this data is meant for
+    pub fn reset_type(&self) -> ResetType {
+        self.reset_type
+    }
+
+    /// The serialized reset data
+    pub fn as_bytes(&self) -> &'buf [u8] {
+        self.data
+    }
+}
@@ -0,0 +1,644 @@
+//! Secure Boot state, and the signature databases it relies on.
+
+use super::{RuntimeServices, Time, Variable, VariableAttributes};
+use crate::alloc_api::vec::Vec;
+use crate::result::Error;
+use crate::{Guid, Result, Status};
+use core::convert::TryFrom;
+
+/// Vendor GUID of the `db`, `dbx`, `dbt` and `dbr` variables.
+pub const IMAGE_SECURITY_DATABASE_GUID: Guid = Guid::from_values(
+    0xd719b2cb,
+    0x3d3a,
+    0x4596,
+    0xa3bc,
+    [0xda, 0xd0, 0x0e, 0x67, 0x65, 0x6f],
+);
+
+/// A signature database used by Secure Boot
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SignatureDatabase {
+    /// The platform key, which authorizes updates to `KEK`.
+    Pk,
+    /// The key exchange keys, which authorize updates to `db` and `dbx`.
+    Kek,
+    /// The signatures and hashes of the images which may be run.
+    Db,
+    /// The signatures and hashes of the images which must not be run.
+    Dbx,
+}
+
+impl SignatureDatabase {
+    /// Name of the variable holding the database
+    pub fn name(self) -> &'static str {
+        match self {
+            SignatureDatabase::Pk => "PK",
+            SignatureDatabase::Kek => "KEK",
+            SignatureDatabase::Db => "db",
+            SignatureDatabase::Dbx => "dbx",
+        }
+    }
+
+    /// Vendor GUID of the variable holding the database
+    pub fn vendor_guid(self) -> Guid {
+        match self {
+            SignatureDatabase::Pk | SignatureDatabase::Kek => Variable::GLOBAL_VARIABLE_GUID,
+            SignatureDatabase::Db | SignatureDatabase::Dbx => IMAGE_SECURITY_DATABASE_GUID,
+        }
+    }
+}
+
+/// The kind of signatures stored in a `SignatureList`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SignatureKind {
+    /// SHA-256 hash of an image.
+    Sha256,
+    /// RSA-2048 public key modulus.
+    Rsa2048,
+    /// RSA-2048 signature of a SHA-256 hash.
+    Rsa2048Sha256,
+    /// SHA-1 hash of an image.
+    Sha1,
+    /// RSA-2048 signature of a SHA-1 hash.
+    Rsa2048Sha1,
+    /// DER-encoded X.509 certificate.
+    X509,
+    /// SHA-224 hash of an image.
+    Sha224,
+    /// SHA-384 hash of an image.
+    Sha384,
+    /// SHA-512 hash of an image.
+    Sha512,
+    /// SHA-256 hash of an X.509 certificate's to-be-signed contents,
+    /// followed by the time of revocation.
+    X509Sha256,
+    /// SHA-384 hash of an X.509 certificate's to-be-signed contents,
+    /// followed by the time of revocation.
+    X509Sha384,
+    /// SHA-512 hash of an X.509 certificate's to-be-signed contents,
+    /// followed by the time of revocation.
+    X509Sha512,
+    /// A kind of signature which is not known to this library.
+    Unknown(Guid),
+}
+
+impl SignatureKind {
+    /// Identifies a kind of signature from its GUID
+    pub fn from_guid(guid: Guid) -> Self {
+        SIGNATURE_KINDS
+            .iter()
+            .find(|(_, kind_guid, _)| *kind_guid == guid)
+            .map_or(SignatureKind::Unknown(guid), |(kind, _, _)| *kind)
+    }
+
+    /// The GUID identifying this kind of signature
+    pub fn guid(self) -> Guid {
+        match self {
+            SignatureKind::Unknown(guid) => guid,
+            kind => SIGNATURE_KINDS
+                .iter()
+                .find(|(known, _, _)| *known == kind)
+                .map(|(_, guid, _)| *guid)
+                .unwrap(),
+        }
+    }
+
+    /// Size of the signature data of this kind, excluding the owner GUID,
+    /// or `None` if it varies between signatures
+    pub fn data_size(self) -> Option<usize> {
+        SIGNATURE_KINDS
+            .iter()
+            .find(|(known, _, _)| *known == self)
+            .and_then(|(_, _, size)| *size)
+    }
+}
+
+/// Known signature kinds, with their GUID and their data size
+const SIGNATURE_KINDS: [(SignatureKind, Guid, Option<usize>); 12] = [
+    (
+        SignatureKind::Sha256,
+        Guid::from_values(
+            0xc1c41626,
+            0x504c,
+            0x4092,
+            0xaca9,
+            [0x41, 0xf9, 0x36, 0x93, 0x43, 0x28],
+        ),
+        Some(32),
+    ),
+    (
+        SignatureKind::Rsa2048,
+        Guid::from_values(
+            0x3c5766e8,
+            0x269c,
+            0x4e34,
+            0xaa14,
+            [0xed, 0x77, 0x6e, 0x85, 0xb3, 0xb6],
+        ),
+        Some(256),
+    ),
+    (
+        SignatureKind::Rsa2048Sha256,
+        Guid::from_values(
+            0xe2b36190,
+            0x879b,
+            0x4a3d,
+            0xad8d,
+            [0xf2, 0xe7, 0xbb, 0xa3, 0x27, 0x84],
+        ),
+        Some(256),
+    ),
+    (
+        SignatureKind::Sha1,
+        Guid::from_values(
+            0x826ca512,
+            0xcf10,
+            0x4ac9,
+            0xb187,
+            [0xbe, 0x01, 0x49, 0x66, 0x31, 0xbd],
+        ),
+        Some(20),
+    ),
+    (
+        SignatureKind::Rsa2048Sha1,
+        Guid::from_values(
+            0x67f8444f,
+            0x8743,
+            0x48f1,
+            0xa328,
+            [0x1e, 0xaa, 0xb8, 0x73, 0x60, 0x80],
+        ),
+        Some(256),
+    ),
+    (
+        SignatureKind::X509,
+        Guid::from_values(
+            0xa5c059a1,
+            0x94e4,
+            0x4aa7,
+            0x87b5,
+            [0xab, 0x15, 0x5c, 0x2b, 0xf0, 0x72],
+        ),
+        None,
+    ),
+    (
+        SignatureKind::Sha224,
+        Guid::from_values(
+            0x0b6e5233,
+            0xa65c,
+            0x44c9,
+            0x9407,
+            [0xd9, 0xab, 0x83, 0xbf, 0xc8, 0xbd],
+        ),
+        Some(28),
+    ),
+    (
+        SignatureKind::Sha384,
+        Guid::from_values(
+            0xff3e5307,
+            0x9fd0,
+            0x48c9,
+            0x85f1,
+            [0x8a, 0xd5, 0x6c, 0x70, 0x1e, 0x01],
+        ),
+        Some(48),
+    ),
+    (
+        SignatureKind::Sha512,
+        Guid::from_values(
+            0x093e0fae,
+            0xa6c4,
+            0x4f50,
+            0x9f1b,
+            [0xd4, 0x1e, 0x2b, 0x89, 0xc1, 0x9a],
+        ),
+        Some(64),
+    ),
+    (
+        SignatureKind::X509Sha256,
+        Guid::from_values(
+            0x3bd2a492,
+            0x96c0,
+            0x4079,
+            0xb420,
+            [0xfc, 0xf9, 0x8e, 0xf1, 0x03, 0xed],
+        ),
+        Some(32 + 16),
+    ),
+    (
+        SignatureKind::X509Sha384,
+        Guid::from_values(
+            0x7076876e,
+            0x80c2,
+            0x4ee6,
+            0xaad2,
+            [0x28, 0xb3, 0x49, 0xa6, 0x86, 0x5b],
+        ),
+        Some(48 + 16),
+    ),
+    (
+        SignatureKind::X509Sha512,
+        Guid::from_values(
+            0x446dbf63,
+            0x2502,
+            0x4cda,
+            0xbcfa,
+            [0x24, 0x65, 0xd2, 0xb0, 0xfe, 0x9d],
+        ),
+        Some(64 + 16),
+    ),
+];
+
+/// Errors which can occur when parsing an `EFI_SIGNATURE_LIST`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SignatureListError {
+    /// The data is shorter than a signature list header.
+    TooShort,
+    /// The size of the list is inconsistent with the data or the sizes of
+    /// its header and signatures.
+    InvalidListSize(u32),
+    /// The signature size is too small to hold the owner GUID, or does not
+    /// match the signature kind.
+    InvalidSignatureSize(u32),
+}
+
+/// Size of the fixed part of an `EFI_SIGNATURE_LIST`
+const SIGNATURE_LIST_HEADER_SIZE: usize = 16 + 3 * 4;
+
+/// Size of the owner GUID at the start of an `EFI_SIGNATURE_DATA`
+const SIGNATURE_OWNER_SIZE: usize = 16;
+
+/// A signature, and the agent which added it to a database
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SignatureData {
+    /// Identifies the agent which added the signature.
+    pub owner: Guid,
+    /// The signature, in the format given by the list's `SignatureKind`.
+    pub data: Vec<u8>,
+}
+
+/// An `EFI_SIGNATURE_LIST`, a group of signatures of the same kind
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SignatureList {
+    kind: SignatureKind,
+    header: Vec<u8>,
+    signature_size: usize,
+    signatures: Vec<SignatureData>,
+}
+
+impl SignatureList {
+    /// Builds a list of signatures of the same kind and size
+    ///
+    /// Returns `InvalidSignatureSize` if the signatures' data do not all have
+    /// the same size, or a size that does not match `kind`.
+    pub fn new(
+        kind: SignatureKind,
+        signatures: Vec<SignatureData>,
+    ) -> core::result::Result<Self, SignatureListError> {
+        let data_size = signatures
+            .first()
+            .map_or(kind.data_size().unwrap_or(0), |signature| {
+                signature.data.len()
+            });
+        let signature_size = SIGNATURE_OWNER_SIZE + data_size;
+        let invalid_size = signatures.iter().any(|s| s.data.len() != data_size)
+            || kind.data_size().map_or(false, |size| size != data_size);
+        if invalid_size || u32::try_from(signature_size).is_err() {
+            return Err(SignatureListError::InvalidSignatureSize(
+                signature_size as u32,
+            ));
+        }
+
+        Ok(SignatureList {
+            kind,
+            header: Vec::new(),
+            signature_size,
+            signatures,
+        })
+    }
+
+    /// Parses the concatenated signature lists stored in a signature
+    /// database variable
+    pub fn parse_all(mut data: &[u8]) -> core::result::Result<Vec<Self>, SignatureListError> {
+        let mut lists = Vec::new();
+        while !data.is_empty() {
+            let (list, rest) = Self::parse(data)?;
+            lists.push(list);
+            data = rest;
+        }
+        Ok(lists)
+    }
+
+    /// Parses a signature list, and returns it with the data which follows it
+    pub fn parse(data: &[u8]) -> core::result::Result<(Self, &[u8]), SignatureListError> {
+        if data.len() < SIGNATURE_LIST_HEADER_SIZE {
+            return Err(SignatureListError::TooShort);
+        }
+        let read_u32 = |offset: usize| {
+            u32::from_le_bytes([
+                data[offset],
+                data[offset + 1],
+                data[offset + 2],
+                data[offset + 3],
+            ])
+        };
+        let mut guid = [0; 16];
+        guid.copy_from_slice(&data[..16]);
+        let kind = SignatureKind::from_guid(Guid::from_bytes(guid));
+        let list_size = read_u32(16);
+        let header_size = read_u32(20);
+        let signature_size = read_u32(24);
+
+        if (signature_size as usize) < SIGNATURE_OWNER_SIZE
+            || kind.data_size().map_or(false, |size| {
+                size + SIGNATURE_OWNER_SIZE != signature_size as usize
+            })
+        {
+            return Err(SignatureListError::InvalidSignatureSize(signature_size));
+        }
+        let signatures_size = (list_size as usize)
+            .checked_sub(SIGNATURE_LIST_HEADER_SIZE)
+            .and_then(|size| size.checked_sub(header_size as usize))
+            .filter(|size| size % signature_size as usize == 0)
+            .filter(|_| list_size as usize <= data.len())
+            .ok_or(SignatureListError::InvalidListSize(list_size))?;
+
+        let header_end = SIGNATURE_LIST_HEADER_SIZE + header_size as usize;
+        let header = data[SIGNATURE_LIST_HEADER_SIZE..header_end].into();
+        let signatures = data[header_end..header_end + signatures_size]
+            .chunks_exact(signature_size as usize)
+            .map(|signature| {
+                let mut owner = [0; 16];
+                owner.copy_from_slice(&signature[..SIGNATURE_OWNER_SIZE]);
+                SignatureData {
+                    owner: Guid::from_bytes(owner),
+                    data: signature[SIGNATURE_OWNER_SIZE..].into(),
+                }
+            })
+            .collect();
+
+        let list = SignatureList {
+            kind,
+            header,
+            signature_size: signature_size as usize,
+            signatures,
+        };
+        Ok((list, &data[list_size as usize..]))
+    }
+
+    /// Serializes the signature list, so that it can be written to a
+    /// signature database variable
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let list_size = SIGNATURE_LIST_HEADER_SIZE
+            + self.header.len()
+            + self.signature_size * self.signatures.len();
+        let mut data = Vec::with_capacity(list_size);
+        data.extend_from_slice(&self.kind.guid().to_bytes());
+        data.extend_from_slice(&(list_size as u32).to_le_bytes());
+        data.extend_from_slice(&(self.header.len() as u32).to_le_bytes());
+        data.extend_from_slice(&(self.signature_size as u32).to_le_bytes());
+        data.extend_from_slice(&self.header);
+        for signature in &self.signatures {
+            data.extend_from_slice(&signature.owner.to_bytes());
+            data.extend_from_slice(&signature.data);
+        }
+        data
+    }
+
+    /// The kind of the signatures in the list
+    pub fn kind(&self) -> SignatureKind {
+        self.kind
+    }
+
+    /// The header of the list, whose format depends on its kind
+    ///
+    /// None of the signature kinds defined by the specification use a
+    /// header.
+    pub fn header(&self) -> &[u8] {
+        &self.header
+    }
+
+    /// The signatures in the list
+    pub fn signatures(&self) -> &[SignatureData] {
+        &self.signatures
+    }
+}
+
+/// GUID of PKCS#7 signatures in a `WIN_CERTIFICATE_UEFI_GUID`
+pub const CERT_TYPE_PKCS7_GUID: Guid = Guid::from_values(
+    0x4aafd29d,
+    0x68df,
+    0x49ee,
+    0x8aa9,
+    [0x34, 0x7d, 0x37, 0x56, 0x65, 0xa7],
+);
+
+/// Revision of the `WIN_CERTIFICATE` structure
+const WIN_CERT_REVISION: u16 = 0x0200;
+
+/// Certificate type of a `WIN_CERTIFICATE_UEFI_GUID`
+const WIN_CERT_TYPE_EFI_GUID: u16 = 0x0EF1;
+
+/// Size of the `WIN_CERTIFICATE_UEFI_GUID` fields preceding the signature
+const WIN_CERTIFICATE_UEFI_GUID_HEADER_SIZE: usize = 4 + 2 + 2 + 16;
+
+/// Serializes a timestamp for an authenticated variable
+///
+/// Only the date and time are used. The nanosecond, time zone and daylight
+/// fields must be zero.
+fn timestamp_bytes(time: &Time) -> [u8; 16] {
+    let mut bytes = [0; 16];
+    bytes[0..2].copy_from_slice(&time.year.to_le_bytes());
+    bytes[2] = time.month;
+    bytes[3] = time.day;
+    bytes[4] = time.hour;
+    bytes[5] = time.minute;
+    bytes[6] = time.second;
+    bytes
+}
+
+/// Builds the data of a write to a variable with the
+/// `TIME_BASED_AUTHENTICATED_WRITE_ACCESS` attribute
+///
+/// The data starts with an `EFI_VARIABLE_AUTHENTICATION_2` descriptor, made
+/// of a timestamp and a PKCS#7 signature of the data returned by
+/// `signed_data`, and is followed by the payload.
+#[derive(Debug, Clone)]
+pub struct AuthenticatedVariableBuilder<'a> {
+    timestamp: [u8; 16],
+    signature: &'a [u8],
+    append_write: bool,
+}
+
+impl<'a> AuthenticatedVariableBuilder<'a> {
+    /// Starts building an authenticated write, from the timestamp which was
+    /// signed and a DER-encoded PKCS#7 `SignedData` structure
+    pub fn new(timestamp: &Time, signature: &'a [u8]) -> Self {
+        AuthenticatedVariableBuilder {
+            timestamp: timestamp_bytes(timestamp),
+            signature,
+            append_write: false,
+        }
+    }
+
+    /// Sets whether the payload is appended to the variable's current data,
+    /// instead of replacing it
+    pub fn append_write(mut self, append_write: bool) -> Self {
+        self.append_write = append_write;
+        self
+    }
+
+    /// Adds the attributes required for this write to `attributes`
+    pub fn attributes(&self, attributes: VariableAttributes) -> VariableAttributes {
+        let mut attributes = attributes | VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS;
+        attributes.set(VariableAttributes::APPEND_WRITE, self.append_write);
+        attributes
+    }
+
+    /// Builds the data to pass to `set_variable`
+    pub fn build(&self, payload: &[u8]) -> Vec<u8> {
+        let certificate_size = WIN_CERTIFICATE_UEFI_GUID_HEADER_SIZE + self.signature.len();
+        let mut data = Vec::with_capacity(16 + certificate_size + payload.len());
+        data.extend_from_slice(&self.timestamp);
+        data.extend_from_slice(&(certificate_size as u32).to_le_bytes());
+        data.extend_from_slice(&WIN_CERT_REVISION.to_le_bytes());
+        data.extend_from_slice(&WIN_CERT_TYPE_EFI_GUID.to_le_bytes());
+        data.extend_from_slice(&CERT_TYPE_PKCS7_GUID.to_bytes());
+        data.extend_from_slice(self.signature);
+        data.extend_from_slice(payload);
+        data
+    }
+
+    /// Returns the data which must be signed to authenticate a write
+    ///
+    /// This is the concatenation of the variable's name without its NUL
+    /// terminator, its vendor GUID, the attributes (including
+    /// `TIME_BASED_AUTHENTICATED_WRITE_ACCESS`, and `APPEND_WRITE` if it is
+    /// used), the timestamp and the payload.
+    pub fn signed_data(
+        variable_name: &str,
+        vendor_guid: &Guid,
+        attributes: VariableAttributes,
+        timestamp: &Time,
+        payload: &[u8],
+    ) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        ucs2::encode_with(variable_name, |c| {
+            data.extend_from_slice(&c.to_le_bytes());
+            Ok(())
+        })?;
+        data.extend_from_slice(&vendor_guid.to_bytes());
+        data.extend_from_slice(&attributes.bits().to_le_bytes());
+        data.extend_from_slice(&timestamp_bytes(timestamp));
+        data.extend_from_slice(payload);
+        Ok(data.into())
+    }
+}
+
+/// Attributes of the signature database variables
+const SIGNATURE_DATABASE_ATTRIBUTES: VariableAttributes = VariableAttributes::from_bits_truncate(
+    VariableAttributes::NON_VOLATILE.bits()
+        | VariableAttributes::BOOTSERVICE_ACCESS.bits()
+        | VariableAttributes::RUNTIME_ACCESS.bits(),
+);
+
+impl RuntimeServices {
+    /// Writes a variable with the `TIME_BASED_AUTHENTICATED_WRITE_ACCESS`
+    /// attribute
+    ///
+    /// # Errors
+    ///
+    /// - `SecurityViolation` if the signature could not be verified, or if
+    ///   the timestamp is not later than the one of the previous write
+    pub fn set_authenticated_variable(
+        &self,
+        variable_name: &str,
+        vendor_guid: &Guid,
+        attributes: VariableAttributes,
+        authentication: &AuthenticatedVariableBuilder<'_>,
+        payload: &[u8],
+    ) -> Result {
+        self.set_variable(
+            variable_name,
+            vendor_guid,
+            authentication.attributes(attributes),
+            &authentication.build(payload),
+        )
+    }
+
+    /// Updates a signature database with serialized `SignatureList`s
+    ///
+    /// Use `AuthenticatedVariableBuilder::append_write` to add signatures to
+    /// a database instead of replacing its contents.
+    pub fn update_signature_database(
+        &self,
+        database: SignatureDatabase,
+        authentication: &AuthenticatedVariableBuilder<'_>,
+        payload: &[u8],
+    ) -> Result {
+        self.set_authenticated_variable(
+            database.name(),
+            &database.vendor_guid(),
+            SIGNATURE_DATABASE_ATTRIBUTES,
+            authentication,
+            payload,
+        )
+    }
+
+    /// Reads a boolean Secure Boot state variable
+    fn secure_boot_flag(&self, name: &str) -> Result<bool> {
+        let (data, _) = self
+            .get_variable(name, &Variable::GLOBAL_VARIABLE_GUID)?
+            .log();
+        match data.as_slice() {
+            [0] => Ok(false.into()),
+            [1] => Ok(true.into()),
+            _ => Err(Status::COMPROMISED_DATA.into()),
+        }
+    }
+
+    /// Whether the firmware is enforcing Secure Boot
+    pub fn secure_boot(&self) -> Result<bool> {
+        self.secure_boot_flag("SecureBoot")
+    }
+
+    /// Whether the platform is in setup mode, where no platform key is
+    /// enrolled
+    pub fn setup_mode(&self) -> Result<bool> {
+        self.secure_boot_flag("SetupMode")
+    }
+
+    /// Whether the platform is in audit mode, where image verification
+    /// failures are logged instead of enforced
+    ///
+    /// Returns a `NotFound` error on firmware predating UEFI 2.5.
+    pub fn audit_mode(&self) -> Result<bool> {
+        self.secure_boot_flag("AuditMode")
+    }
+
+    /// Whether the platform is in deployed mode, the most secure mode
+    ///
+    /// Returns a `NotFound` error on firmware predating UEFI 2.5.
+    pub fn deployed_mode(&self) -> Result<bool> {
+        self.secure_boot_flag("DeployedMode")
+    }
+
+    /// Reads and parses a signature database
+    ///
+    /// A database which does not exist is returned as an empty list. Parsing
+    /// errors are reported as `CompromisedData` errors, which carry the cause
+    /// of the error.
+    pub fn signature_database(
+        &self,
+        database: SignatureDatabase,
+    ) -> Result<Vec<SignatureList>, Option<SignatureListError>> {
+        let data = match self.get_variable(database.name(), &database.vendor_guid()) {
+            Ok(completion) => completion.log().0,
+            Err(err) if err.status() == Status::NOT_FOUND => Vec::new(),
+            Err(err) => return Err(Error::new(err.status(), None)),
+        };
+        SignatureList::parse_all(&data)
+            .map(Into::into)
+            .map_err(|err| Error::new(Status::COMPROMISED_DATA, Some(err)))
+    }
+}
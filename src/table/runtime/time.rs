@@ -0,0 +1,344 @@
+//! Validation, conversions and arithmetic for `Time`.
+
+use super::{Daylight, Time};
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::fmt;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+use core::time::Duration;
+
+/// Value of `Time::time_zone` for times which are not tied to a time zone
+const UNSPECIFIED_TIMEZONE: i16 = 2047;
+
+const SECONDS_PER_MINUTE: i64 = 60;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+const NANOSECONDS_PER_SECOND: u32 = 1_000_000_000;
+
+/// Errors which can occur when building a `Time`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TimeError {
+    /// The year is not within 1900 and 9999.
+    InvalidYear,
+    /// The month is not within 1 and 12.
+    InvalidMonth,
+    /// The day does not exist in the month.
+    InvalidDay,
+    /// The hour is not within 0 and 23.
+    InvalidHour,
+    /// The minute is not within 0 and 59.
+    InvalidMinute,
+    /// The second is not within 0 and 59.
+    InvalidSecond,
+    /// The nanosecond is not within 0 and 999,999,999.
+    InvalidNanosecond,
+    /// The time zone is not within -1440 and 1440 minutes, nor unspecified.
+    InvalidTimeZone,
+}
+
+/// Whether `year` is a leap year in the Gregorian calendar
+fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Number of days in a month
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days from 1970-01-01 to a date of the proleptic Gregorian
+/// calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Count years from March, so that leap days are at the end of the year.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date of the proleptic Gregorian calendar which is `days` days after
+/// 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    (year, month as u8, day as u8)
+}
+
+/// Offset from UTC of a time zone and daylight saving state, in seconds
+fn utc_offset(time_zone: Option<i16>, daylight: Daylight) -> i64 {
+    let dst = if daylight.contains(Daylight::IN_DAYLIGHT) {
+        60
+    } else {
+        0
+    };
+    (i64::from(time_zone.unwrap_or(0)) + dst) * SECONDS_PER_MINUTE
+}
+
+impl Time {
+    /// Checks that every field is in range, and that the day exists
+    pub(super) fn validate(&self) -> Result<(), TimeError> {
+        if !(1900..=9999).contains(&self.year) {
+            Err(TimeError::InvalidYear)
+        } else if !(1..=12).contains(&self.month) {
+            Err(TimeError::InvalidMonth)
+        } else if self.day < 1 || self.day > days_in_month(self.year, self.month) {
+            Err(TimeError::InvalidDay)
+        } else if self.hour > 23 {
+            Err(TimeError::InvalidHour)
+        } else if self.minute > 59 {
+            Err(TimeError::InvalidMinute)
+        } else if self.second > 59 {
+            Err(TimeError::InvalidSecond)
+        } else if self.nanosecond >= NANOSECONDS_PER_SECOND {
+            Err(TimeError::InvalidNanosecond)
+        } else if !(-1440..=1440).contains(&self.time_zone)
+            && self.time_zone != UNSPECIFIED_TIMEZONE
+        {
+            Err(TimeError::InvalidTimeZone)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether every field is in range, and the day exists
+    ///
+    /// Times produced by the firmware, such as file timestamps, are not
+    /// checked and may be invalid. Conversions and comparisons of invalid
+    /// times give meaningless results.
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /// Seconds since 1970-01-01T00:00:00 in this time's own time zone
+    fn local_seconds(&self) -> i64 {
+        let days = days_from_civil(
+            i64::from(self.year),
+            i64::from(self.month),
+            i64::from(self.day),
+        );
+        days * SECONDS_PER_DAY
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * SECONDS_PER_MINUTE
+            + i64::from(self.second)
+    }
+
+    /// Builds a time from seconds since 1970-01-01T00:00:00 in the given
+    /// time zone
+    fn from_local_seconds(
+        seconds: i64,
+        nanosecond: u32,
+        time_zone: i16,
+        daylight: Daylight,
+    ) -> Result<Self, TimeError> {
+        let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        if !(1900..=9999).contains(&year) {
+            return Err(TimeError::InvalidYear);
+        }
+        let seconds_of_day = seconds.rem_euclid(SECONDS_PER_DAY);
+        Time::new(
+            year as u16,
+            month,
+            day,
+            (seconds_of_day / 3600) as u8,
+            (seconds_of_day / 60 % 60) as u8,
+            (seconds_of_day % 60) as u8,
+            nanosecond,
+            time_zone,
+            daylight,
+        )
+    }
+
+    /// Builds a UTC time from a number of seconds since the Unix epoch, and
+    /// a number of nanoseconds within that second
+    pub fn from_unix_timestamp(timestamp: i64, nanosecond: u32) -> Result<Self, TimeError> {
+        if nanosecond >= NANOSECONDS_PER_SECOND {
+            return Err(TimeError::InvalidNanosecond);
+        }
+        Self::from_local_seconds(timestamp, nanosecond, 0, Daylight::empty())
+    }
+
+    /// Returns the number of seconds since the Unix epoch
+    ///
+    /// The time zone and daylight saving time are taken into account. Times
+    /// with an unspecified time zone are assumed to be in UTC. The
+    /// nanoseconds are not included.
+    pub fn to_unix_timestamp(&self) -> i64 {
+        self.local_seconds() - utc_offset(self.time_zone(), self.daylight)
+    }
+
+    /// Builds a UTC time from the time elapsed since the Unix epoch
+    pub fn from_duration_since_unix_epoch(duration: Duration) -> Result<Self, TimeError> {
+        let seconds = i64::try_from(duration.as_secs()).map_err(|_| TimeError::InvalidYear)?;
+        Self::from_unix_timestamp(seconds, duration.subsec_nanos())
+    }
+
+    /// Returns the time elapsed since the Unix epoch, or `None` if this time
+    /// is earlier than the epoch
+    pub fn duration_since_unix_epoch(&self) -> Option<Duration> {
+        let seconds = u64::try_from(self.to_unix_timestamp()).ok()?;
+        Some(Duration::new(seconds, self.nanosecond))
+    }
+
+    /// Returns the same instant in another time zone
+    ///
+    /// `Daylight::IN_DAYLIGHT` moves the local time one hour ahead.
+    pub fn with_time_zone(
+        &self,
+        time_zone: Option<i16>,
+        daylight: Daylight,
+    ) -> Result<Self, TimeError> {
+        if let Some(time_zone) = time_zone {
+            if !(-1440..=1440).contains(&time_zone) {
+                return Err(TimeError::InvalidTimeZone);
+            }
+        }
+        let seconds = self.to_unix_timestamp() + utc_offset(time_zone, daylight);
+        Self::from_local_seconds(
+            seconds,
+            self.nanosecond,
+            time_zone.unwrap_or(UNSPECIFIED_TIMEZONE),
+            daylight,
+        )
+    }
+
+    /// Returns the time elapsed from `earlier` to this time, or `None` if
+    /// `earlier` is later than this time
+    pub fn duration_since(&self, earlier: &Time) -> Option<Duration> {
+        let mut seconds = self.to_unix_timestamp() - earlier.to_unix_timestamp();
+        let nanosecond = if self.nanosecond >= earlier.nanosecond {
+            self.nanosecond - earlier.nanosecond
+        } else {
+            seconds -= 1;
+            self.nanosecond + NANOSECONDS_PER_SECOND - earlier.nanosecond
+        };
+        let seconds = u64::try_from(seconds).ok()?;
+        Some(Duration::new(seconds, nanosecond))
+    }
+
+    /// Adds a duration to this time, keeping its time zone, or returns
+    /// `None` if the result is after the year 9999
+    pub fn checked_add(&self, duration: Duration) -> Option<Time> {
+        let mut seconds = i64::try_from(duration.as_secs()).ok()?;
+        let mut nanosecond = self.nanosecond + duration.subsec_nanos();
+        if nanosecond >= NANOSECONDS_PER_SECOND {
+            nanosecond -= NANOSECONDS_PER_SECOND;
+            seconds = seconds.checked_add(1)?;
+        }
+        let seconds = self.local_seconds().checked_add(seconds)?;
+        Self::from_local_seconds(seconds, nanosecond, self.time_zone, self.daylight).ok()
+    }
+
+    /// Subtracts a duration from this time, keeping its time zone, or
+    /// returns `None` if the result is before the year 1900
+    pub fn checked_sub(&self, duration: Duration) -> Option<Time> {
+        let mut seconds = i64::try_from(duration.as_secs()).ok()?;
+        let nanosecond = if self.nanosecond >= duration.subsec_nanos() {
+            self.nanosecond - duration.subsec_nanos()
+        } else {
+            seconds = seconds.checked_add(1)?;
+            self.nanosecond + NANOSECONDS_PER_SECOND - duration.subsec_nanos()
+        };
+        let seconds = self.local_seconds().checked_sub(seconds)?;
+        Self::from_local_seconds(seconds, nanosecond, self.time_zone, self.daylight).ok()
+    }
+}
+
+/// Times are compared by the instant they represent, regardless of their
+/// time zone.
+impl PartialEq for Time {
+    fn eq(&self, other: &Time) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Time {}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Time) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Time {
+    fn cmp(&self, other: &Time) -> Ordering {
+        (self.to_unix_timestamp(), self.nanosecond)
+            .cmp(&(other.to_unix_timestamp(), other.nanosecond))
+    }
+}
+
+impl Add<Duration> for Time {
+    type Output = Time;
+
+    fn add(self, duration: Duration) -> Time {
+        self.checked_add(duration)
+            .expect("overflow when adding duration to time")
+    }
+}
+
+impl AddAssign<Duration> for Time {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Time {
+    type Output = Time;
+
+    fn sub(self, duration: Duration) -> Time {
+        self.checked_sub(duration)
+            .expect("overflow when subtracting duration from time")
+    }
+}
+
+impl SubAssign<Duration> for Time {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+/// Formats the time in the ISO-8601 format, such as
+/// `2021-03-04T05:06:07.000000008+01:00`.
+///
+/// The fractional seconds are omitted if they are zero, and the offset is
+/// omitted if the time zone is unspecified.
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )?;
+        if self.nanosecond != 0 {
+            write!(f, ".{:09}", self.nanosecond)?;
+        }
+        if let Some(time_zone) = self.time_zone() {
+            let offset = utc_offset(Some(time_zone), self.daylight) / SECONDS_PER_MINUTE;
+            if offset == 0 {
+                write!(f, "Z")?;
+            } else {
+                let sign = if offset < 0 { '-' } else { '+' };
+                let offset = offset.abs();
+                write!(f, "{}{:02}:{:02}", sign, offset / 60, offset % 60)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Variables which hold a value of a given type.
+
+use super::VariableAttributes;
+use crate::Guid;
+use core::{mem, ptr, slice};
+
+#[cfg(feature = "exts")]
+use super::RuntimeServices;
+#[cfg(feature = "exts")]
+use crate::alloc_api::vec;
+#[cfg(feature = "exts")]
+use crate::{Result, Status};
+
+/// Plain data types, which are stored as their raw bytes
+///
+/// `usize` and `isize` are not plain data: their size depends on the target,
+/// while variables may be read back by firmware or OS code built for
+/// another one.
+///
+/// # Safety
+///
+/// Implementors must not contain any padding bytes, and every byte pattern
+/// of the right size must be a valid value of the type.
+pub unsafe trait PlainData: Copy {
+    /// Views the value as raw bytes
+    fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self as *const Self as *const u8, mem::size_of::<Self>()) }
+    }
+
+    /// Reads a value from raw bytes
+    ///
+    /// Returns `None` if `bytes` does not have the size of the type.
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != mem::size_of::<Self>() {
+            return None;
+        }
+        Some(unsafe { ptr::read_unaligned(bytes.as_ptr() as *const Self) })
+    }
+}
+
+macro_rules! impl_plain_data {
+    ($($ty:ty),*) => {
+        $(unsafe impl PlainData for $ty {})*
+    };
+}
+
+impl_plain_data!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, Guid);
+
+unsafe impl<T: PlainData, const N: usize> PlainData for [T; N] {}
+
+/// A variable which holds a value of the implementing type
+///
+/// This trait can be derived for `#[repr(C)]` structs whose fields are all
+/// `PlainData`, and which have no padding. The variable is described by a
+/// `variable` attribute, where only the vendor GUID is mandatory:
+///
+/// ```
+/// #[derive(Clone, Copy, UefiVariable)]
+/// #[repr(C)]
+/// #[variable(
+///     vendor = "12345678-9abc-def0-1234-56789abcdef0",
+///     name = "DummyConfig",
+///     attributes = "NON_VOLATILE | BOOTSERVICE_ACCESS"
+/// )]
+/// struct DummyConfig {
+///     version: u32,
+///     flags: u32,
+/// }
+/// ```
+///
+/// The name defaults to the name of the type, and the attributes default to
+/// `NON_VOLATILE | BOOTSERVICE_ACCESS | RUNTIME_ACCESS`.
+pub trait UefiVariable: Sized {
+    /// Name of the variable
+    const NAME: &'static str;
+
+    /// GUID of the variable's vendor
+    const VENDOR: Guid;
+
+    /// Attributes used when writing the variable
+    const ATTRIBUTES: VariableAttributes = VariableAttributes::from_bits_truncate(
+        VariableAttributes::NON_VOLATILE.bits()
+            | VariableAttributes::BOOTSERVICE_ACCESS.bits()
+            | VariableAttributes::RUNTIME_ACCESS.bits(),
+    );
+
+    /// Size of the variable's data, in bytes
+    fn encoded_len(&self) -> usize;
+
+    /// Writes the variable's data into `buffer`, which is `encoded_len()`
+    /// bytes long
+    fn encode(&self, buffer: &mut [u8]);
+
+    /// Reads a value from the variable's data
+    ///
+    /// Returns `None` if the data is not a valid value.
+    fn decode(data: &[u8]) -> Option<Self>;
+}
+
+pub use uefi_macros::UefiVariable;
+
+#[cfg(feature = "exts")]
+impl RuntimeServices {
+    /// Reads a typed variable
+    ///
+    /// # Errors
+    ///
+    /// - `CompromisedData` if the variable's data is not a valid value
+    pub fn read<T: UefiVariable>(&self) -> Result<T> {
+        let (data, _) = self.get_variable(T::NAME, &T::VENDOR)?.log();
+        T::decode(&data)
+            .map(Into::into)
+            .ok_or_else(|| Status::COMPROMISED_DATA.into())
+    }
+
+    /// Writes a typed variable, with its default attributes
+    pub fn write<T: UefiVariable>(&self, value: &T) -> Result {
+        let mut data = vec![0; value.encoded_len()];
+        value.encode(&mut data);
+        self.set_variable(T::NAME, &T::VENDOR, T::ATTRIBUTES, &data)
+    }
+}
@@ -0,0 +1,259 @@
+//! Enumeration of EFI variable names.
+
+use super::RuntimeServices;
+use crate::result::Error;
+use crate::{CStr16, Guid, Result, Status};
+
+#[cfg(feature = "exts")]
+use crate::alloc_api::{vec, vec::Vec};
+
+/// Enumerates the names of EFI variables into a caller-provided buffer
+///
+/// This does not require an allocator. Since each name borrows the buffer,
+/// this cannot be an `Iterator`: call `next_name` until it returns `None`.
+pub struct VariableNames<'rt, 'buf> {
+    rt: &'rt RuntimeServices,
+    buffer: &'buf mut [u16],
+    vendor: Guid,
+    filter: Option<Guid>,
+}
+
+impl<'rt, 'buf> VariableNames<'rt, 'buf> {
+    pub(super) fn new(rt: &'rt RuntimeServices, buffer: &'buf mut [u16]) -> Self {
+        // An empty name starts the enumeration
+        if let Some(first) = buffer.first_mut() {
+            *first = 0;
+        }
+        VariableNames {
+            rt,
+            buffer,
+            vendor: Guid::from_bytes([0; 16]),
+            filter: None,
+        }
+    }
+
+    /// Only enumerates the variables of the given vendor
+    pub fn vendor(mut self, vendor: Guid) -> Self {
+        self.filter = Some(vendor);
+        self
+    }
+
+    /// Reads the name and vendor GUID of the next variable into the buffer
+    ///
+    /// Returns `None` once all variables have been enumerated.
+    ///
+    /// # Errors
+    ///
+    /// * `uefi::Status::BUFFER_TOO_SMALL`  The buffer is too small to hold the next name, the
+    ///                                     required buffer size (in characters) is provided into
+    ///                                     the error.
+    /// * `uefi::Status::COMPROMISED_DATA`  The name is not a valid UCS-2 string. Calling
+    ///                                     `next_name` again skips it, use `next_raw_name` to
+    ///                                     read such names.
+    /// * `uefi::Status::DEVICE_ERROR`      The variable store could not be read.
+    pub fn next_name(&mut self) -> Result<Option<(&CStr16, Guid)>, Option<usize>> {
+        if !next_variable(self.rt, self.buffer, &mut self.vendor, self.filter)? {
+            return Ok(None.into());
+        }
+        let vendor = self.vendor;
+        let name = name_with_nul(self.buffer)?;
+        CStr16::from_u16_with_nul(name)
+            .map(|name| Some((name, vendor)).into())
+            .map_err(|_| Error::new(Status::COMPROMISED_DATA, None))
+    }
+
+    /// Reads the name and vendor GUID of the next variable into the buffer,
+    /// and returns the name as the firmware stored it, without its NUL
+    /// terminator
+    ///
+    /// Unlike `next_name`, this also returns names which are not valid UCS-2
+    /// strings, so that every variable can be accessed.
+    ///
+    /// # Errors
+    ///
+    /// * `uefi::Status::BUFFER_TOO_SMALL`  The buffer is too small to hold the next name, the
+    ///                                     required buffer size (in characters) is provided into
+    ///                                     the error.
+    /// * `uefi::Status::COMPROMISED_DATA`  The name is not NUL-terminated.
+    /// * `uefi::Status::DEVICE_ERROR`      The variable store could not be read.
+    pub fn next_raw_name(&mut self) -> Result<Option<(&[u16], Guid)>, Option<usize>> {
+        if !next_variable(self.rt, self.buffer, &mut self.vendor, self.filter)? {
+            return Ok(None.into());
+        }
+        let vendor = self.vendor;
+        let name = name_with_nul(self.buffer)?;
+        Ok(Some((&name[..name.len() - 1], vendor)).into())
+    }
+
+    /// Continues the enumeration into a larger buffer
+    ///
+    /// This is meant to be called after `next_name` or `next_raw_name`
+    /// failed with `BUFFER_TOO_SMALL`: the current name and vendor GUID are
+    /// copied to `buffer`, so that the next call reads the name which did not
+    /// fit.
+    ///
+    /// Returns `self` unchanged if `buffer` cannot hold the current name.
+    pub fn with_buffer<'new>(
+        self,
+        buffer: &'new mut [u16],
+    ) -> core::result::Result<VariableNames<'rt, 'new>, Self> {
+        // An empty buffer could not hold any name, so the enumeration has
+        // not started yet.
+        let name = if self.buffer.is_empty() {
+            &[0][..]
+        } else {
+            match name_with_nul::<()>(self.buffer) {
+                Ok(name) => name,
+                Err(_) => return Err(self),
+            }
+        };
+        if buffer.len() < name.len() {
+            return Err(self);
+        }
+        buffer[..name.len()].copy_from_slice(name);
+
+        Ok(VariableNames {
+            rt: self.rt,
+            buffer,
+            vendor: self.vendor,
+            filter: self.filter,
+        })
+    }
+}
+
+/// Advances `buffer` and `vendor` to the next variable matching `filter`
+///
+/// Returns `false` once all variables have been enumerated.
+fn next_variable(
+    rt: &RuntimeServices,
+    buffer: &mut [u16],
+    vendor: &mut Guid,
+    filter: Option<Guid>,
+) -> core::result::Result<bool, Error<Option<usize>>> {
+    loop {
+        match rt.get_next_variable_name(buffer, vendor) {
+            (Status::SUCCESS, _) if filter.map_or(true, |filter| filter == *vendor) => {
+                return Ok(true)
+            }
+            (Status::SUCCESS, _) => {}
+            (Status::NOT_FOUND, _) => return Ok(false),
+            (Status::BUFFER_TOO_SMALL, len) => {
+                return Err(Error::new(Status::BUFFER_TOO_SMALL, Some(len)))
+            }
+            (status, _) => return Err(Error::new(status, None)),
+        }
+    }
+}
+
+/// Finds the NUL-terminated name returned by the firmware in `buffer`
+fn name_with_nul<Data: core::fmt::Debug + Default>(
+    buffer: &[u16],
+) -> core::result::Result<&[u16], Error<Data>> {
+    buffer
+        .iter()
+        .position(|&c| c == 0)
+        .map(|nul| &buffer[..=nul])
+        .ok_or_else(|| Error::new(Status::COMPROMISED_DATA, Data::default()))
+}
+
+/// The name and vendor GUID of an EFI variable
+#[cfg(feature = "exts")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableKey {
+    name: Vec<u16>,
+    vendor: Guid,
+}
+
+#[cfg(feature = "exts")]
+impl VariableKey {
+    /// The name of the variable, or `None` if it is not a valid UCS-2
+    /// string
+    pub fn name(&self) -> Option<&CStr16> {
+        CStr16::from_u16_with_nul(&self.name).ok()
+    }
+
+    /// The name of the variable as the firmware stored it, without its NUL
+    /// terminator
+    pub fn raw_name(&self) -> &[u16] {
+        &self.name[..self.name.len() - 1]
+    }
+
+    /// The GUID of the variable's vendor
+    pub fn vendor(&self) -> Guid {
+        self.vendor
+    }
+}
+
+/// An iterator over the names of EFI variables, which grows its buffer as
+/// needed
+///
+/// Iteration stops after the first error, except for names which are not
+/// NUL-terminated: those are reported as `Status::COMPROMISED_DATA` and
+/// skipped. Names which are not valid UCS-2 strings are returned, and can be
+/// read with `VariableKey::raw_name`.
+#[cfg(feature = "exts")]
+pub struct VariableKeys<'rt> {
+    rt: &'rt RuntimeServices,
+    buffer: Vec<u16>,
+    vendor: Guid,
+    filter: Option<Guid>,
+    done: bool,
+}
+
+#[cfg(feature = "exts")]
+impl<'rt> VariableKeys<'rt> {
+    pub(super) fn new(rt: &'rt RuntimeServices) -> Self {
+        VariableKeys {
+            rt,
+            buffer: vec![0; 32],
+            vendor: Guid::from_bytes([0; 16]),
+            filter: None,
+            done: false,
+        }
+    }
+
+    /// Only yields the variables of the given vendor
+    pub fn vendor(mut self, vendor: Guid) -> Self {
+        self.filter = Some(vendor);
+        self
+    }
+}
+
+#[cfg(feature = "exts")]
+impl<'rt> Iterator for VariableKeys<'rt> {
+    type Item = Result<VariableKey>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            match next_variable(self.rt, &mut self.buffer, &mut self.vendor, self.filter) {
+                Ok(true) => break,
+                Ok(false) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(err) => match err.split() {
+                    // The firmware left the previous name in the buffer
+                    (Status::BUFFER_TOO_SMALL, Some(len)) if len > self.buffer.len() => {
+                        self.buffer.resize(len, 0)
+                    }
+                    (status, _) => {
+                        self.done = true;
+                        return Some(Err(status.into()));
+                    }
+                },
+            }
+        }
+
+        let vendor = self.vendor;
+        Some(name_with_nul(&self.buffer).map(|name| {
+            VariableKey {
+                name: name.to_vec(),
+                vendor,
+            }
+            .into()
+        }))
+    }
+}
@@ -1,12 +1,14 @@
+use core::fmt;
 use core::marker::PhantomData;
 use core::slice;
 
 use crate::proto::console::text;
+use crate::result::Error;
 use crate::{CStr16, Char16, Handle, Result, ResultExt, Status};
 
-use super::boot::{BootServices, MemoryDescriptor};
+use super::boot::{BootServices, MemoryAttribute, MemoryDescriptor, PAGE_SIZE};
 use super::runtime::RuntimeServices;
-use super::{cfg, Header, Revision};
+use super::{cfg, Header, HeaderError, Revision};
 
 /// Marker trait used to provide different views of the UEFI System Table
 pub trait SystemTableView {}
@@ -42,32 +44,37 @@ impl SystemTableView for Runtime {}
 /// will be provided to replace it.
 #[repr(transparent)]
 pub struct SystemTable<View: SystemTableView> {
-    table: &'static SystemTableImpl,
+    table: *const SystemTableImpl,
     _marker: PhantomData<View>,
 }
 
 // These parts of the UEFI System Table interface will always be available
 impl<View: SystemTableView> SystemTable<View> {
+    /// The underlying table, which is valid for as long as this view is
+    fn table(&self) -> &SystemTableImpl {
+        unsafe { &*self.table }
+    }
+
     /// Return the firmware vendor string
     pub fn firmware_vendor(&self) -> &CStr16 {
-        unsafe { CStr16::from_ptr(self.table.fw_vendor) }
+        unsafe { CStr16::from_ptr(self.table().fw_vendor) }
     }
 
     /// Return the firmware revision
     pub fn firmware_revision(&self) -> Revision {
-        self.table.fw_revision
+        self.table().fw_revision
     }
 
     /// Returns the revision of this table, which is defined to be
     /// the revision of the UEFI specification implemented by the firmware.
     pub fn uefi_revision(&self) -> Revision {
-        self.table.header.revision
+        self.table().header.revision
     }
 
     /// Returns the config table entries, a linear array of structures
     /// pointing to other system-specific tables.
     pub fn config_table(&self) -> &[cfg::ConfigTableEntry] {
-        unsafe { slice::from_raw_parts(self.table.cfg_table, self.table.nr_cfg) }
+        unsafe { slice::from_raw_parts(self.table().cfg_table, self.table().nr_cfg) }
     }
 }
 
@@ -77,29 +84,29 @@ impl<View: SystemTableView> SystemTable<View> {
 impl SystemTable<Boot> {
     /// Returns the standard input protocol.
     pub fn stdin(&self) -> &mut text::Input {
-        unsafe { &mut *self.table.stdin }
+        unsafe { &mut *self.table().stdin }
     }
 
     /// Returns the standard output protocol.
     pub fn stdout(&self) -> &mut text::Output {
-        let stdout_ptr = self.table.stdout as *const _ as *mut _;
+        let stdout_ptr = self.table().stdout as *const _ as *mut _;
         unsafe { &mut *stdout_ptr }
     }
 
     /// Returns the standard error protocol.
     pub fn stderr(&self) -> &mut text::Output {
-        let stderr_ptr = self.table.stderr as *const _ as *mut _;
+        let stderr_ptr = self.table().stderr as *const _ as *mut _;
         unsafe { &mut *stderr_ptr }
     }
 
     /// Access runtime services
     pub fn runtime_services(&self) -> &RuntimeServices {
-        self.table.runtime
+        self.table().runtime
     }
 
     /// Access boot services
     pub fn boot_services(&self) -> &BootServices {
-        unsafe { &*self.table.boot }
+        unsafe { &*self.table().boot }
     }
 
     /// Exit the UEFI boot services
@@ -173,6 +180,28 @@ impl SystemTable<Boot> {
         }
     }
 
+    /// Returns the next value of the platform's 64-bit monotonic counter.
+    ///
+    /// See `BootServices::get_next_monotonic_count` for details. The counts
+    /// returned by `SystemTable<Runtime>::next_monotonic_count` after exiting
+    /// boot services are always larger than the ones returned here.
+    pub fn next_monotonic_count(&self) -> Result<u64> {
+        self.boot_services().get_next_monotonic_count()
+    }
+
+    /// Validates the headers of the system, boot services and runtime
+    /// services tables.
+    ///
+    /// This checks their signatures, revisions and CRCs, and can be used to
+    /// detect corrupted or spoofed tables.
+    pub fn validate(&self) -> core::result::Result<(), HeaderError> {
+        unsafe {
+            self.table().header.validate::<Self>()?;
+            (*self.table().boot).header.validate::<BootServices>()?;
+            self.table().runtime.header.validate::<RuntimeServices>()
+        }
+    }
+
     /// Clone this boot-time UEFI system table interface
     ///
     /// # Safety
@@ -201,7 +230,87 @@ impl SystemTable<Runtime> {
     /// CPU configuration which may not be preserved by OS loaders. See the
     /// "Calling Conventions" chapter of the UEFI specification for details.
     pub unsafe fn runtime_services(&self) -> &RuntimeServices {
-        self.table.runtime
+        self.table().runtime
+    }
+
+    /// Switches the runtime services to virtual addressing, and returns a
+    /// view of the system table at its new virtual address.
+    ///
+    /// Each entry of `map` must have its `virt_start` set to the virtual
+    /// address at which the range will be mapped. Only the ranges with the
+    /// `MemoryAttribute::RUNTIME` attribute need to be mapped. During this
+    /// call, the firmware converts the pointers of the system table, such as
+    /// the runtime services and configuration table pointers, to virtual
+    /// addresses.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidParameter` if the system table is not in a runtime range of
+    ///   `map`, or if `map` is otherwise invalid
+    /// - `Unsupported` if the firmware is already using virtual addresses
+    /// - `NoMapping` or `NotFound` if a runtime range is missing from `map`
+    ///
+    /// The error carries back the system table, which is still usable with
+    /// physical addresses.
+    ///
+    /// # Safety
+    ///
+    /// This must be called while the memory is identity-mapped, and can only
+    /// be called once. The returned view of the system table, and the
+    /// runtime services it gives access to, can only be used once the
+    /// virtual mapping described by `map` is active.
+    pub unsafe fn set_virtual_address_map(
+        self,
+        map: &mut [MemoryDescriptor],
+    ) -> Result<Self, Self> {
+        let table = self.table as u64;
+        let virtual_table = map
+            .iter()
+            .filter(|desc| desc.att.contains(MemoryAttribute::RUNTIME))
+            .find(|desc| {
+                let size = desc.page_count * PAGE_SIZE as u64;
+                table >= desc.phys_start && table - desc.phys_start < size
+            })
+            .map(|desc| desc.virt_start + (table - desc.phys_start));
+        let virtual_table = match virtual_table {
+            Some(virtual_table) => virtual_table,
+            None => return Err(Error::new(Status::INVALID_PARAMETER, self)),
+        };
+
+        match self.runtime_services().set_virtual_address_map(map) {
+            // The new address is not mapped yet, so it must not be
+            // dereferenced here.
+            Ok(completion) => Ok(completion.map(|_| SystemTable {
+                table: virtual_table as usize as *const SystemTableImpl,
+                _marker: PhantomData,
+            })),
+            Err(err) => Err(Error::new(err.status(), self)),
+        }
+    }
+
+    /// Returns the next value of the platform's 64-bit monotonic counter.
+    ///
+    /// Only the high 32 bits of the counter can be incremented once boot
+    /// services are exited, so every call increments the high count and
+    /// returns it with the low 32 bits cleared. This keeps the counter
+    /// monotonic with respect to the values returned during boot, and across
+    /// resets.
+    ///
+    /// # Safety
+    ///
+    /// This has the same requirements as `runtime_services`.
+    pub unsafe fn next_monotonic_count(&self) -> Result<u64> {
+        self.runtime_services()
+            .get_next_high_monotonic_count()
+            .map_inner(|high| u64::from(high) << 32)
+    }
+}
+
+impl<View: SystemTableView> fmt::Debug for SystemTable<View> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SystemTable")
+            .field("table", &self.table)
+            .finish()
     }
 }
 
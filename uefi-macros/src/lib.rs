@@ -5,7 +5,10 @@ extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::{quote, TokenStreamExt};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse_macro_input, DeriveInput, Generics, Ident, ItemFn, ItemType, LitStr};
+use syn::{
+    parse_macro_input, Data, DeriveInput, Generics, Ident, ItemFn, ItemType, Lit, LitStr, Meta,
+    NestedMeta,
+};
 
 /// Parses a type definition, extracts its identifier and generic parameters
 struct TypeDefinition {
@@ -31,15 +34,8 @@ impl Parse for TypeDefinition {
     }
 }
 
-/// `unsafe_guid` attribute macro, implements the `Identify` trait for any type
-/// (mostly works like a custom derive, but also supports type aliases)
-#[proc_macro_attribute]
-pub fn unsafe_guid(args: TokenStream, input: TokenStream) -> TokenStream {
-    // Parse the arguments and input using Syn
-    let guid_str = parse_macro_input!(args as LitStr).value();
-    let mut result: proc_macro2::TokenStream = input.clone().into();
-    let type_definition = parse_macro_input!(input as TypeDefinition);
-
+/// Parses a GUID in canonical textual format into the arguments of `Guid::from_values`
+fn parse_guid(guid_str: &str) -> (u32, u16, u16, u16, [u8; 6]) {
     // We expect a canonical GUID string, such as "12345678-9abc-def0-fedc-ba9876543210"
     if guid_str.len() != 36 {
         panic!(
@@ -83,6 +79,27 @@ pub fn unsafe_guid(args: TokenStream, input: TokenStream) -> TokenStream {
         (node_64 % 0x100) as u8,
     ];
 
+    (
+        time_low,
+        time_mid,
+        time_high_and_version,
+        clock_seq_and_variant,
+        node,
+    )
+}
+
+/// `unsafe_guid` attribute macro, implements the `Identify` trait for any type
+/// (mostly works like a custom derive, but also supports type aliases)
+#[proc_macro_attribute]
+pub fn unsafe_guid(args: TokenStream, input: TokenStream) -> TokenStream {
+    // Parse the arguments and input using Syn
+    let guid_str = parse_macro_input!(args as LitStr).value();
+    let mut result: proc_macro2::TokenStream = input.clone().into();
+    let type_definition = parse_macro_input!(input as TypeDefinition);
+
+    let (time_low, time_mid, time_high_and_version, clock_seq_and_variant, node) =
+        parse_guid(&guid_str);
+
     // At this point, we know everything we need to implement Identify
     let ident = type_definition.ident.clone();
     let (impl_generics, ty_generics, where_clause) = type_definition.generics.split_for_impl();
@@ -124,6 +141,136 @@ pub fn derive_protocol(item: TokenStream) -> TokenStream {
     result.into()
 }
 
+/// Custom derive for the `UefiVariable` trait
+///
+/// The variable is described by a `#[variable(...)]` attribute, whose
+/// `vendor` GUID is mandatory, and whose `name` and `attributes` are optional.
+#[proc_macro_derive(UefiVariable, attributes(variable))]
+pub fn derive_uefi_variable(item: TokenStream) -> TokenStream {
+    // Parse the input using Syn
+    let item = parse_macro_input!(item as DeriveInput);
+    let ident = item.ident.clone();
+
+    // Only plain data structs can be stored as raw bytes
+    let fields = match item.data {
+        Data::Struct(data) => data.fields,
+        _ => panic!("UefiVariable can only be derived for structs"),
+    };
+    if !item.generics.params.is_empty() {
+        panic!("UefiVariable cannot be derived for generic types");
+    }
+    let is_repr_c = item.attrs.iter().any(|attr| {
+        if !attr.path.is_ident("repr") {
+            return false;
+        }
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    path.is_ident("C") || path.is_ident("transparent")
+                }
+                _ => false,
+            }),
+            _ => false,
+        }
+    });
+    if !is_repr_c {
+        panic!("UefiVariable can only be derived for #[repr(C)] structs");
+    }
+
+    // Parse the description of the variable
+    let mut name = ident.to_string();
+    let mut vendor = None;
+    let mut attributes = None;
+    for attr in item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("variable"))
+    {
+        let list = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list,
+            _ => panic!("Expected #[variable(key = \"value\", ...)]"),
+        };
+        for nested in list.nested {
+            let (key, value) = match nested {
+                NestedMeta::Meta(Meta::NameValue(pair)) => match pair.lit {
+                    Lit::Str(value) => (pair.path, value.value()),
+                    _ => panic!("Variable properties must be string literals"),
+                },
+                _ => panic!("Expected #[variable(key = \"value\", ...)]"),
+            };
+            if key.is_ident("name") {
+                name = value;
+            } else if key.is_ident("vendor") {
+                vendor = Some(value);
+            } else if key.is_ident("attributes") {
+                attributes = Some(value);
+            } else {
+                panic!("Unknown variable property, expected name, vendor or attributes");
+            }
+        }
+    }
+    let vendor = vendor.expect("The vendor GUID must be given by #[variable(vendor = \"...\")]");
+    let (time_low, time_mid, time_high_and_version, clock_seq_and_variant, node) =
+        parse_guid(&vendor);
+
+    // Attributes are given as `VariableAttributes` flag names, such as "NON_VOLATILE | BOOTSERVICE_ACCESS"
+    let attributes = attributes.map(|attributes| {
+        let flags = attributes
+            .split('|')
+            .map(|flag| Ident::new(flag.trim(), proc_macro2::Span::call_site()));
+        quote! {
+            const ATTRIBUTES: uefi::table::runtime::VariableAttributes =
+                uefi::table::runtime::VariableAttributes::from_bits_truncate(
+                    0 #(| uefi::table::runtime::VariableAttributes::#flags.bits())*
+                );
+        }
+    });
+
+    let field_types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let result = quote! {
+        // Every field must be plain data, and must not be followed by padding
+        const _: () = {
+            fn assert_plain_data<T: uefi::table::runtime::PlainData>() {}
+            #[allow(dead_code)]
+            fn assert_fields() {
+                #(assert_plain_data::<#field_types>();)*
+            }
+        };
+        const _: [(); 0] = [(); ::core::mem::size_of::<#ident>()
+            - (0 #(+ ::core::mem::size_of::<#field_types>())*)];
+
+        unsafe impl uefi::table::runtime::PlainData for #ident {}
+
+        impl uefi::table::runtime::UefiVariable for #ident {
+            const NAME: &'static str = #name;
+
+            #[allow(clippy::unreadable_literal)]
+            const VENDOR: uefi::Guid = uefi::Guid::from_values(
+                #time_low,
+                #time_mid,
+                #time_high_and_version,
+                #clock_seq_and_variant,
+                [#(#node),*],
+            );
+
+            #attributes
+
+            fn encoded_len(&self) -> usize {
+                ::core::mem::size_of::<Self>()
+            }
+
+            fn encode(&self, buffer: &mut [u8]) {
+                buffer.copy_from_slice(uefi::table::runtime::PlainData::as_bytes(self))
+            }
+
+            fn decode(data: &[u8]) -> Option<Self> {
+                uefi::table::runtime::PlainData::from_bytes(data)
+            }
+        }
+    };
+    result.into()
+}
+
 /// Custom attribute for a UEFI executable entrypoint
 #[proc_macro_attribute]
 pub fn entry(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -25,8 +25,8 @@ use core::ptr::NonNull;
 use cfg_if::cfg_if;
 
 use uefi::prelude::*;
-use uefi::table::boot::{EventType, Tpl};
-use uefi::table::{Boot, SystemTable};
+use uefi::table::boot::{EventType, Tpl, EVENT_GROUP_EXIT_BOOT_SERVICES};
+use uefi::table::{Boot, Revision, SystemTable};
 use uefi::{Event, Result};
 
 /// Reference to the system table.
@@ -75,14 +75,25 @@ pub fn init(st: &SystemTable<Boot>) -> Result {
         init_logger(st);
         uefi::alloc::init(boot_services);
 
-        // Schedule these tools to be disabled on exit from UEFI boot services
-        boot_services
-            .create_event(
+        // Schedule these tools to be disabled on exit from UEFI boot services,
+        // using the event group mechanism on firmware which supports it
+        let event = if st.uefi_revision() >= Revision::new(2, 0) {
+            boot_services.create_event_ex(
+                EventType::NOTIFY_SIGNAL,
+                Tpl::NOTIFY,
+                Some(exit_boot_services),
+                Some(&EVENT_GROUP_EXIT_BOOT_SERVICES),
+            )
+        } else {
+            boot_services.create_event(
                 EventType::SIGNAL_EXIT_BOOT_SERVICES,
                 Tpl::NOTIFY,
                 Some(exit_boot_services),
             )
-            .map_inner(|_| ())
+        };
+        event.map_inner(|event| {
+            event.into_raw();
+        })
     }
 }
 
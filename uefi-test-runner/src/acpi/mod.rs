@@ -0,0 +1,85 @@
+use core::ffi::c_void;
+use core::ptr;
+use uefi::prelude::*;
+use uefi::table::acpi::{AcpiError, AcpiTables, LocalApicFlags, MadtEntry, McfgEntry, Sdt};
+
+pub fn test(st: &SystemTable<Boot>) {
+    info!("Testing ACPI tables");
+
+    // The tables live in ACPI reclaim memory, which is identity-mapped until
+    // boot services are exited.
+    let tables = unsafe { AcpiTables::from_config_table(st.config_table()) }
+        .expect("Failed to find the ACPI tables");
+    info!("RSDP revision: {}", tables.rsdp().revision());
+    for table in tables.tables() {
+        let table = table.expect("Found a corrupted ACPI table");
+        info!(
+            "Found ACPI table {}",
+            core::str::from_utf8(&table.signature()).unwrap_or("????")
+        );
+    }
+
+    let fadt = tables
+        .fadt()
+        .expect("The FADT is missing")
+        .expect("The FADT is corrupted");
+    let dsdt = unsafe { Sdt::from_address(fadt.dsdt_address() as usize as *const c_void) }
+        .expect("The DSDT is corrupted");
+    assert_eq!(&dsdt.signature(), b"DSDT");
+
+    // Null entries must be rejected rather than dereferenced
+    let err = unsafe { Sdt::from_address(ptr::null()) }
+        .expect_err("A null table address should be rejected");
+    assert_eq!(err, AcpiError::NullAddress);
+
+    let madt = tables
+        .madt()
+        .expect("The MADT is missing")
+        .expect("The MADT is corrupted");
+    let processors = madt
+        .entries()
+        .filter(|entry| match entry {
+            MadtEntry::LocalApic(apic) => apic.flags.contains(LocalApicFlags::ENABLED),
+            MadtEntry::LocalX2Apic(apic) => apic.flags.contains(LocalApicFlags::ENABLED),
+            MadtEntry::Gicc(gicc) => gicc.flags & 1 != 0,
+            _ => false,
+        })
+        .count();
+    info!("Found {} enabled processors", processors);
+    assert!(processors > 0, "The MADT lists no enabled processor");
+
+    // The other tables depend on the emulated machine
+    if let Some(mcfg) = tables.mcfg() {
+        for entry in mcfg.expect("The MCFG is corrupted").entries() {
+            info!("PCIe ECAM region: {:x?}", entry);
+            assert!(entry.start_bus <= entry.end_bus);
+            assert_eq!(
+                entry.function_address(entry.start_bus, 0, 0),
+                Some(entry.base_address + (u64::from(entry.start_bus) << 20))
+            );
+        }
+    }
+    let entry = McfgEntry {
+        base_address: u64::MAX - 0xfff,
+        segment_group: 0,
+        start_bus: 0,
+        end_bus: 255,
+    };
+    assert_eq!(
+        entry.function_address(1, 0, 0),
+        None,
+        "Addresses past the end of the address space should be rejected"
+    );
+    if let Some(hpet) = tables.hpet() {
+        let hpet = hpet.expect("The HPET table is corrupted");
+        info!("HPET registers: {:x?}", hpet.base_address());
+    }
+    if let Some(spcr) = tables.spcr() {
+        let spcr = spcr.expect("The SPCR is corrupted");
+        info!("Serial console registers: {:x?}", spcr.base_address());
+    }
+    if let Some(bgrt) = tables.bgrt() {
+        let bgrt = bgrt.expect("The BGRT is corrupted");
+        info!("Boot logo at {:#x}", bgrt.image_address());
+    }
+}
@@ -1,11 +1,22 @@
 use uefi::prelude::*;
-use uefi::table::boot::{BootServices, EventType, TimerTrigger, Tpl};
+use uefi::table::boot::{BootServices, EventType, MemoryType, OwnedEvent, TimerTrigger, Tpl};
+use uefi::table::crc32;
+use uefi::Guid;
 
-pub fn test(bt: &BootServices) {
+pub fn test(st: &SystemTable<Boot>) {
+    let bt = st.boot_services();
     info!("Testing timer...");
     test_timer(bt);
+    info!("Testing events...");
+    test_event(bt);
     info!("Testing watchdog...");
     test_watchdog(bt);
+    info!("Testing configuration table...");
+    test_config_table(st);
+    info!("Testing CRC32...");
+    test_crc32(bt);
+    info!("Testing monotonic counter...");
+    test_monotonic_count(st);
 }
 
 fn test_watchdog(bt: &BootServices) {
@@ -17,9 +28,95 @@ fn test_watchdog(bt: &BootServices) {
 fn test_timer(bt: &BootServices) {
     let timer_event = unsafe { bt.create_event(EventType::TIMER, Tpl::APPLICATION, None) }
         .expect_success("Failed to create TIMER event");
-    let mut events = [timer_event];
-    bt.set_timer(timer_event, TimerTrigger::Relative(5_0 /*00 ns */))
+    bt.set_timer(&timer_event, TimerTrigger::Relative(5_0 /*00 ns */))
         .expect_success("Failed to set timer");
+    let mut events = [timer_event.raw()];
     bt.wait_for_event(&mut events)
         .expect_success("Wait for event failed");
 }
+
+fn test_event(bt: &BootServices) {
+    let event = unsafe { bt.create_event(EventType::empty(), Tpl::APPLICATION, None) }
+        .expect_success("Failed to create event");
+
+    let signaled = bt
+        .check_event(&event)
+        .expect_success("Failed to check event");
+    assert_eq!(signaled, None, "A new event should not be signaled");
+
+    bt.signal_event(&event)
+        .expect_success("Failed to signal event");
+    let signaled = bt
+        .check_event(&event)
+        .expect_success("Failed to check event");
+    assert_eq!(signaled, Some(()), "The event should be signaled");
+
+    // Checking the event clears its signaled state
+    let signaled = bt
+        .check_event(&event)
+        .expect_success("Failed to check event");
+    assert_eq!(signaled, None, "The event should not be signaled anymore");
+
+    // An event given up with `into_raw` can be taken back and closed
+    let raw_event = event.into_raw();
+    let event = unsafe { OwnedEvent::from_raw(bt, raw_event) };
+    bt.close_event(event)
+        .expect_success("Failed to close event");
+}
+
+fn test_config_table(st: &SystemTable<Boot>) {
+    const TEST_TABLE_GUID: Guid = Guid::from_values(
+        0x6c1ac7a2,
+        0x3cb8,
+        0x4e1c,
+        0x9a4d,
+        [0x55, 0x01, 0x8b, 0xd7, 0x43, 0x22],
+    );
+
+    let bt = st.boot_services();
+    let table = bt
+        .allocate_pool(MemoryType::ACPI_RECLAIM, 16)
+        .expect_success("Failed to allocate table");
+    unsafe { bt.install_configuration_table(&TEST_TABLE_GUID, table as *const _) }
+        .expect_success("Failed to install configuration table");
+
+    let entry = st
+        .config_table()
+        .iter()
+        .find(|entry| entry.guid == TEST_TABLE_GUID)
+        .expect("Installed configuration table not found");
+    assert_eq!(entry.address, table as *const _);
+
+    let err = unsafe { bt.install_configuration_table(&TEST_TABLE_GUID, core::ptr::null()) }
+        .expect_error("A null table should be rejected");
+    assert_eq!(err.status(), Status::INVALID_PARAMETER);
+
+    bt.remove_configuration_table(&TEST_TABLE_GUID)
+        .expect_success("Failed to remove configuration table");
+    assert!(
+        st.config_table()
+            .iter()
+            .all(|entry| entry.guid != TEST_TABLE_GUID),
+        "Configuration table was not removed"
+    );
+    bt.free_pool(table).expect_success("Failed to free table");
+}
+
+fn test_crc32(bt: &BootServices) {
+    let data = b"123456789";
+    let crc = bt
+        .calculate_crc32(data)
+        .expect_success("Failed to compute CRC32");
+    assert_eq!(crc, 0xcbf4_3926, "Firmware computed an unexpected CRC32");
+    assert_eq!(crc32(data), crc, "Software and firmware CRC32 differ");
+}
+
+fn test_monotonic_count(st: &SystemTable<Boot>) {
+    let first = st
+        .next_monotonic_count()
+        .expect_success("Failed to get monotonic count");
+    let second = st
+        .next_monotonic_count()
+        .expect_success("Failed to get monotonic count");
+    assert!(second > first, "Monotonic count did not increase");
+}
@@ -1,9 +1,10 @@
-use uefi::table::boot::BootServices;
+use uefi::prelude::*;
 
-pub fn test(bt: &BootServices) {
+pub fn test(st: &SystemTable<Boot>) {
     info!("Testing boot services");
+    let bt = st.boot_services();
     memory::test(bt);
-    misc::test(bt);
+    misc::test(st);
 }
 
 mod memory;
@@ -11,13 +11,16 @@ extern crate alloc;
 // Keep this line to ensure the `mem*` functions are linked in.
 extern crate rlibc;
 
+use alloc::vec::Vec;
 use core::mem;
 use uefi::prelude::*;
 use uefi::proto::console::serial::Serial;
 use uefi::table::boot::MemoryDescriptor;
 
+mod acpi;
 mod boot;
 mod proto;
+mod runtime;
 
 #[entry]
 fn efi_main(image: Handle, st: SystemTable<Boot>) -> Status {
@@ -32,6 +35,9 @@ fn efi_main(image: Handle, st: SystemTable<Boot>) -> Status {
     // Ensure the tests are run on a version of UEFI we support.
     check_revision(st.uefi_revision());
 
+    // Ensure the tables we were given are not corrupted.
+    st.validate().expect("The system tables are corrupted");
+
     // Test all the boot services.
     let bt = st.boot_services();
 
@@ -40,14 +46,18 @@ fn efi_main(image: Handle, st: SystemTable<Boot>) -> Status {
         .expect("Failed to retrieve boot file system")
         .unwrap();
 
-    boot::test(bt);
+    boot::test(&st);
 
     // Test all the supported protocols.
-    proto::test(&st);
+    proto::test(image, &st);
 
-    // TODO: test the runtime services.
+    // Test the runtime services.
     // These work before boot services are exited, but we'd probably want to
     // test them after exit_boot_services...
+    runtime::test(&st);
+
+    // Test the ACPI tables published through the configuration table.
+    acpi::test(&st);
 
     shutdown(image, st);
 }
@@ -121,14 +131,23 @@ fn shutdown(image: uefi::Handle, st: SystemTable<Boot>) -> ! {
         info!("Testing complete, shutting down...");
     }
 
+    // Get notified when the runtime services switch to virtual addressing
+    runtime::register_virtual_address_change(&st);
+
     // Exit boot services as a proof that it works :)
     let max_mmap_size =
         st.boot_services().memory_map_size() + 8 * mem::size_of::<MemoryDescriptor>();
     let mut mmap_storage = vec![0; max_mmap_size].into_boxed_slice();
-    let (st, _iter) = st
+    // Nothing can be allocated once boot services are exited
+    let mut descriptors = Vec::with_capacity(max_mmap_size / mem::size_of::<MemoryDescriptor>());
+    let (st, iter) = st
         .exit_boot_services(image, &mut mmap_storage[..])
         .expect_success("Failed to exit boot services");
 
+    // The firmware's descriptors may be larger than `MemoryDescriptor`
+    descriptors.extend(iter.copied());
+    let st = runtime::set_virtual_address_map(st, &mut descriptors);
+
     // Shut down the system
     let rt = unsafe { st.runtime_services() };
     rt.reset(ResetType::Shutdown, Status::SUCCESS, None);
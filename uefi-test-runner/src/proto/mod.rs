@@ -1,13 +1,20 @@
+use alloc::boxed::Box;
 use uefi::prelude::*;
 
 use uefi::proto;
+use uefi::proto::loaded_image::LoadedImage;
+use uefi::proto::Protocol;
+use uefi::table::boot::{EventType, OpenProtocolAttributes, SearchType, Tpl};
+use uefi::{Guid, Identify};
 
-pub fn test(st: &SystemTable<Boot>) {
+pub fn test(image: Handle, st: &SystemTable<Boot>) {
     info!("Testing various protocols");
 
     let bt = st.boot_services();
 
     find_protocol(bt);
+    open_protocol(bt, image);
+    install_protocol(bt);
 
     console::test(st);
     debug::test(bt);
@@ -26,6 +33,128 @@ fn find_protocol(bt: &BootServices) {
         handles.len() > 1,
         "There should be at least one implementation of Simple Text Output (stdout)"
     );
+
+    let handle_buffer = bt
+        .locate_handle_buffer(SearchType::from_proto::<SearchedProtocol>())
+        .expect_success("Failed to retrieve buffer of handles");
+
+    assert_eq!(
+        handle_buffer.len(),
+        handles.len(),
+        "Both handle enumeration functions should find the same handles"
+    );
+}
+
+fn open_protocol(bt: &BootServices, image: Handle) {
+    info!("Opening the `LoadedImage` protocol of the running image");
+
+    let exclusive = bt
+        .open_protocol_exclusive::<LoadedImage>(image, image)
+        .expect_success("Failed to open `LoadedImage` protocol exclusively");
+    let err = bt
+        .open_protocol_exclusive::<LoadedImage>(image, image)
+        .expect_error("An exclusively opened protocol should not be opened again");
+    assert_eq!(err.status(), Status::ACCESS_DENIED);
+    drop(exclusive);
+
+    let err = unsafe {
+        bt.open_protocol::<LoadedImage>(image, image, None, OpenProtocolAttributes::TEST_PROTOCOL)
+    }
+    .expect_error("`TEST_PROTOCOL` should be rejected");
+    assert_eq!(err.status(), Status::INVALID_PARAMETER);
+
+    // The protocol is only opened once, and stays installed while it is open
+    let loaded_image = unsafe {
+        bt.open_protocol::<LoadedImage>(image, image, None, OpenProtocolAttributes::GET_PROTOCOL)
+    }
+    .expect_success("Failed to open `LoadedImage` protocol");
+
+    assert!(
+        loaded_image.image_size() > 0,
+        "The running image should not be empty"
+    );
+
+    let open_info = bt
+        .open_protocol_information(image, &LoadedImage::GUID)
+        .expect_success("Failed to retrieve open protocol information");
+    assert!(
+        open_info.iter().any(|entry| entry.agent_handle == image
+            && entry
+                .attributes
+                .contains(OpenProtocolAttributes::GET_PROTOCOL)),
+        "The opened protocol should be recorded in the handle database"
+    );
+
+    // Dropping the guard closes the protocol again
+    drop(loaded_image);
+
+    let protocols = bt
+        .protocols_per_handle(image)
+        .expect_success("Failed to retrieve protocols of the image handle");
+    assert!(
+        protocols.iter().any(|&guid| *guid == LoadedImage::GUID),
+        "The image handle should support the `LoadedImage` protocol"
+    );
+
+    bt.test_protocol::<LoadedImage>(image, image, None)
+        .expect_success("`LoadedImage` should be installed on the image handle");
+}
+
+/// A dummy protocol, used to test protocol installation
+struct TestProtocol {
+    data: u32,
+}
+
+unsafe impl Identify for TestProtocol {
+    const GUID: Guid = Guid::from_values(
+        0x1a972918,
+        0x3f69,
+        0x4b5d,
+        0x8cb4,
+        [0xce, 0xcd, 0x2e, 0x21, 0x26, 0xb5],
+    );
+}
+
+impl Protocol for TestProtocol {}
+
+fn install_protocol(bt: &BootServices) {
+    info!("Installing a custom protocol on a new handle");
+
+    // Get notified when the protocol gets installed
+    let event = unsafe { bt.create_event(EventType::empty(), Tpl::NOTIFY, None) }
+        .expect_success("Failed to create event");
+    let key = bt
+        .register_protocol_notify::<TestProtocol>(&event)
+        .expect_success("Failed to register protocol notification");
+
+    let interface = Box::leak(Box::new(TestProtocol { data: 123 }));
+    let interface_ptr = interface as *const TestProtocol;
+    let handle = bt
+        .install_protocol_interface(None, interface)
+        .expect_success("Failed to install protocol interface");
+
+    let found = bt
+        .locate_handle(SearchType::from_proto::<TestProtocol>(), None)
+        .expect_success("Failed to look up the installed protocol");
+    assert_eq!(
+        found, 1,
+        "The installed protocol should be found exactly once"
+    );
+
+    let mut new_handles = bt.registered_handles(key);
+    assert_eq!(new_handles.next(), Some(handle), "Missing notification");
+    assert!(
+        new_handles.next().is_none(),
+        "Handles should only be reported once"
+    );
+
+    let located = bt
+        .handle_protocol::<TestProtocol>(handle)
+        .expect_success("Failed to open the installed protocol");
+    assert_eq!(unsafe { (*located.get()).data }, 123);
+
+    unsafe { bt.uninstall_protocol_interface(handle, interface_ptr) }
+        .expect_success("Failed to uninstall protocol interface");
 }
 
 mod console;
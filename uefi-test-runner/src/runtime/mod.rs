@@ -0,0 +1,612 @@
+use alloc::vec::Vec;
+use core::ptr;
+use core::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+use core::time::Duration;
+use uefi::prelude::*;
+use uefi::table::boot::{BootServices, EventType, MemoryDescriptor, Tpl};
+use uefi::table::runtime::secure_boot::{
+    AuthenticatedVariableBuilder, SignatureData, SignatureDatabase, SignatureKind, SignatureList,
+    CERT_TYPE_PKCS7_GUID,
+};
+use uefi::table::runtime::{
+    CapsuleBuilder, CapsuleFlags, Daylight, LoadOption, LoadOptionAttributes, LoadOptionCategory,
+    ResetData, ResetDataError, ResetType, RuntimeServices, ScatterGatherList, Time, TimeError,
+    UefiVariable, Variable, VariableAttributes,
+};
+use uefi::table::Runtime;
+use uefi::{CStr16, Event};
+
+pub fn test(st: &SystemTable<Boot>) {
+    info!("Testing runtime services");
+    let rt = st.runtime_services();
+    time(rt);
+    wakeup_time(rt);
+    variable_info(rt);
+    capsule(st.boot_services(), rt);
+    boot_options(rt);
+    secure_boot(rt);
+    authenticated_variable(rt);
+    reset_data();
+    os_indications(rt);
+    variable_names(rt);
+    typed_variable(rt);
+}
+
+fn wakeup_time(rt: &RuntimeServices) {
+    info!("Testing the wakeup timer");
+
+    let now = rt.get_time().expect_success("Failed to get time");
+    match rt.get_wakeup_time() {
+        Ok(completion) => {
+            let (enabled, pending, time) = completion.unwrap();
+            info!(
+                "Wakeup timer: enabled={} pending={} time={:?}",
+                enabled, pending, time
+            );
+        }
+        Err(err) if err.status() == Status::UNSUPPORTED => {
+            warn!("The wakeup timer is not supported");
+            return;
+        }
+        Err(err) => panic!("Failed to get wakeup time: {:?}", err),
+    }
+
+    unsafe { rt.set_wakeup_time(&now) }.expect_success("Failed to set wakeup time");
+    let (enabled, _, _) = rt
+        .get_wakeup_time()
+        .expect_success("Failed to get wakeup time");
+    assert!(enabled, "The wakeup timer should be enabled");
+
+    unsafe { rt.disable_wakeup_time() }.expect_success("Failed to disable wakeup time");
+    let (enabled, _, _) = rt
+        .get_wakeup_time()
+        .expect_success("Failed to get wakeup time");
+    assert!(!enabled, "The wakeup timer should be disabled");
+}
+
+fn variable_info(rt: &RuntimeServices) {
+    info!("Testing variable storage information");
+
+    let attributes = VariableAttributes::NON_VOLATILE
+        | VariableAttributes::BOOTSERVICE_ACCESS
+        | VariableAttributes::RUNTIME_ACCESS;
+    let storage = match rt.query_variable_info(attributes) {
+        Ok(completion) => completion.unwrap(),
+        Err(err) if err.status() == Status::UNSUPPORTED => {
+            warn!("Querying variable storage information is not supported");
+            return;
+        }
+        Err(err) => panic!("Failed to query variable storage: {:?}", err),
+    };
+    info!("Variable storage: {:?}", storage);
+    assert!(storage.remaining_variable_storage_size <= storage.maximum_variable_storage_size);
+
+    // A variable larger than the maximum size must be rejected before
+    // reaching the firmware.
+    let vendor = uefi::Guid::from_values(
+        0x8f0a_6c5e,
+        0x5d2b,
+        0x4b4e,
+        0x9a4f,
+        [0x1f, 0x3c, 0x6d, 0x2e, 0x7a, 0x90],
+    );
+    let data = vec![0; storage.maximum_variable_size as usize + 1];
+    let err = rt
+        .set_variable_checked("UefiRsTestVariable", &vendor, attributes, &data)
+        .expect_error("An oversized variable should be rejected");
+    assert_eq!(err.status(), Status::BAD_BUFFER_SIZE);
+    assert_eq!(*err.data(), Some(storage));
+
+    // A variable can be written and deleted with a UCS-2 name.
+    let name = "UefiRsTestVariable\0".encode_utf16().collect::<Vec<_>>();
+    let name = CStr16::from_u16_with_nul(&name).unwrap_or_else(|_| panic!("Invalid variable name"));
+    let attributes = VariableAttributes::BOOTSERVICE_ACCESS;
+    rt.set_variable_cstr16(name, &vendor, attributes, &[1, 2, 3])
+        .expect_success("Failed to set variable");
+    let (data, _) = rt
+        .get_variable("UefiRsTestVariable", &vendor)
+        .expect_success("Failed to read variable");
+    assert_eq!(data, [1, 2, 3]);
+    rt.set_variable_cstr16(name, &vendor, attributes, &[])
+        .expect_success("Failed to delete variable");
+}
+
+fn capsule(bt: &BootServices, rt: &RuntimeServices) {
+    info!("Testing capsule services");
+
+    let guid = uefi::Guid::from_values(
+        0x3b8c_8162,
+        0x188c,
+        0x46a4,
+        0xaec9,
+        [0xbe, 0x43, 0xf1, 0xd6, 0x56, 0x97],
+    );
+    let body = [0x5a; 64];
+    let capsule = CapsuleBuilder::new(guid)
+        .flags(CapsuleFlags::PERSIST_ACROSS_RESET)
+        .guid_flags(0x1234)
+        .header_size(32)
+        .build(bt, &body)
+        .expect_success("Failed to build capsule");
+
+    let header = capsule.header();
+    assert_eq!(header.capsule_guid(), guid);
+    assert_eq!(header.flags(), CapsuleFlags::PERSIST_ACROSS_RESET);
+    assert_eq!(header.guid_flags(), 0x1234);
+    assert_eq!(header.capsule_image_size(), 32 + 64);
+    assert_eq!(&capsule.as_bytes()[32..], &body[..]);
+
+    let list =
+        ScatterGatherList::new(bt, &[header]).expect_success("Failed to build scatter-gather list");
+    assert_ne!(list.address(), 0);
+
+    // The capsule's GUID is made up, so the firmware is not expected to
+    // accept it. It is not passed to `update_capsule`, which could reset
+    // the machine.
+    match rt.query_capsule_capabilities(&[header]) {
+        Ok(completion) => {
+            let (max_size, reset_type) = completion.unwrap();
+            info!(
+                "Capsule capabilities: max size={} reset type={:?}",
+                max_size, reset_type
+            );
+        }
+        Err(err) => info!("Capsule was rejected: {:?}", err.status()),
+    }
+}
+
+fn boot_options(rt: &RuntimeServices) {
+    info!("Testing boot options");
+
+    // The current boot option must survive a round trip through `LoadOption`.
+    let current = rt
+        .boot_current()
+        .expect_success("Failed to read BootCurrent");
+    let option = rt
+        .boot_option(current)
+        .expect_success("Failed to read the current boot option");
+    info!("Booted from Boot{:04X}: {}", current, option.description());
+    let reparsed = LoadOption::parse(&option.to_bytes()).expect("Failed to reparse boot option");
+    assert_eq!(reparsed, option);
+    assert!(option.file_paths().next().is_some());
+
+    // Create a boot option with an empty device path, and remove it again.
+    let end_of_path = vec![0x7f, 0xff, 0x04, 0x00];
+    let option = LoadOption::new(
+        LoadOptionAttributes::ACTIVE | LoadOptionAttributes::CATEGORY_APP,
+        "uefi-rs test option",
+        end_of_path,
+        vec![1, 2, 3],
+    )
+    .expect("Failed to build boot option");
+    let index = rt
+        .create_boot_option(&option)
+        .expect_success("Failed to create boot option");
+    let boot_order = rt.boot_order().expect_success("Failed to read BootOrder");
+    assert_eq!(boot_order.first(), Some(&index));
+    let read_back = rt
+        .boot_option(index)
+        .expect_success("Failed to read the new boot option");
+    assert_eq!(read_back, option);
+    assert_eq!(read_back.attributes().category(), LoadOptionCategory::APP);
+
+    // Reserved attribute bits must not be lost when an option is rewritten.
+    let mut bytes = option.to_bytes();
+    bytes[3] |= 0x80;
+    let reserved = LoadOption::parse(&bytes).expect("Failed to parse boot option");
+    assert_eq!(reserved.to_bytes(), bytes);
+
+    rt.remove_boot_option(index)
+        .expect_success("Failed to remove boot option");
+    let boot_order = rt.boot_order().expect_success("Failed to read BootOrder");
+    assert!(!boot_order.contains(&index));
+    let err = rt
+        .boot_option(index)
+        .expect_error("The boot option should have been deleted");
+    assert_eq!(err.status(), Status::NOT_FOUND);
+}
+
+fn secure_boot(rt: &RuntimeServices) {
+    info!("Testing Secure Boot variables");
+
+    // Signature lists must survive a round trip through their serialized form.
+    let owner = uefi::Guid::from_values(
+        0x6a3e_91c4,
+        0x0f27,
+        0x4d6b,
+        0x8e12,
+        [0x5c, 0x9b, 0x30, 0xa7, 0x41, 0xe8],
+    );
+    let list = SignatureList::new(
+        SignatureKind::Sha256,
+        vec![SignatureData {
+            owner,
+            data: vec![0xab; 32],
+        }],
+    )
+    .expect("Failed to build signature list");
+    let bytes = list.to_bytes();
+    assert_eq!(bytes.len(), 28 + 16 + 32);
+    let lists = SignatureList::parse_all(&bytes).expect("Failed to parse signature list");
+    assert_eq!(lists, [list]);
+
+    match rt.secure_boot() {
+        Ok(enabled) => info!("Secure Boot enabled: {}", enabled.unwrap()),
+        Err(err) if err.status() == Status::NOT_FOUND => {
+            warn!("Secure Boot is not supported");
+            return;
+        }
+        Err(err) => panic!("Failed to read SecureBoot: {:?}", err),
+    }
+    let setup_mode = rt.setup_mode().expect_success("Failed to read SetupMode");
+    info!("Setup mode: {}", setup_mode);
+
+    for &database in &[
+        SignatureDatabase::Pk,
+        SignatureDatabase::Kek,
+        SignatureDatabase::Db,
+        SignatureDatabase::Dbx,
+    ] {
+        let lists = rt
+            .signature_database(database)
+            .expect_success("Failed to read signature database");
+        for list in &lists {
+            info!(
+                "{}: {} signature(s) of kind {:?}",
+                database.name(),
+                list.signatures().len(),
+                list.kind()
+            );
+        }
+        if database == SignatureDatabase::Pk {
+            assert_eq!(lists.is_empty(), setup_mode);
+        }
+    }
+}
+
+fn authenticated_variable(rt: &RuntimeServices) {
+    info!("Testing authenticated variable payloads");
+
+    let timestamp = rt.get_time().expect_success("Failed to get time");
+    let signature = [0x30, 0x82, 0x00, 0x00];
+    let payload = [1, 2, 3, 4, 5];
+    let builder = AuthenticatedVariableBuilder::new(&timestamp, &signature).append_write(true);
+
+    let attributes = builder.attributes(VariableAttributes::NON_VOLATILE);
+    assert!(attributes.contains(
+        VariableAttributes::NON_VOLATILE
+            | VariableAttributes::TIME_BASED_AUTHENTICATED_WRITE_ACCESS
+            | VariableAttributes::APPEND_WRITE
+    ));
+
+    // EFI_TIME, then WIN_CERTIFICATE_UEFI_GUID, then the payload.
+    let data = builder.build(&payload);
+    assert_eq!(data.len(), 16 + 24 + signature.len() + payload.len());
+    assert_eq!(&data[0..2], &timestamp.year().to_le_bytes());
+    assert_eq!(&data[16..20], &(24 + signature.len() as u32).to_le_bytes());
+    assert_eq!(&data[20..24], &[0x00, 0x02, 0xf1, 0x0e]);
+    assert_eq!(&data[24..40], &CERT_TYPE_PKCS7_GUID.to_bytes());
+    assert_eq!(&data[40..44], &signature);
+    assert_eq!(&data[44..], &payload);
+
+    let signed = AuthenticatedVariableBuilder::signed_data(
+        "db",
+        &SignatureDatabase::Db.vendor_guid(),
+        attributes,
+        &timestamp,
+        &payload,
+    )
+    .expect_success("Failed to build signed data");
+    assert_eq!(signed.len(), 4 + 16 + 4 + 16 + payload.len());
+    assert_eq!(&signed[0..4], &[b'd', 0, b'b', 0]);
+}
+
+fn time(rt: &RuntimeServices) {
+    info!("Testing time conversions");
+
+    let now = rt.get_time().expect_success("Failed to get time");
+    assert!(now.is_valid(), "The firmware returned an invalid time");
+    info!("Current time: {}", now);
+
+    // 2000-02-29 is a leap day, 2100-02-29 is not.
+    assert!(Time::new(2000, 2, 29, 0, 0, 0, 0, 0, Daylight::empty()).is_ok());
+    assert_eq!(
+        Time::new(2100, 2, 29, 0, 0, 0, 0, 0, Daylight::empty()).err(),
+        Some(TimeError::InvalidDay)
+    );
+    assert_eq!(
+        Time::new(2021, 1, 1, 24, 0, 0, 0, 0, Daylight::empty()).err(),
+        Some(TimeError::InvalidHour)
+    );
+
+    let time = Time::new(2000, 3, 1, 0, 0, 0, 0, 0, Daylight::empty()).unwrap();
+    assert_eq!(time.to_unix_timestamp(), 951_868_800);
+    assert_eq!(Time::from_unix_timestamp(951_868_800, 0), Ok(time));
+    assert_eq!(
+        time.duration_since_unix_epoch(),
+        Some(Duration::from_secs(951_868_800))
+    );
+
+    // The same instant one hour ahead of UTC, with daylight saving time.
+    let local = time
+        .with_time_zone(Some(0), Daylight::IN_DAYLIGHT)
+        .expect("Failed to convert time zone");
+    assert_eq!(local.hour(), 1);
+    assert_eq!(local, time);
+    assert_eq!(format!("{}", local), "2000-03-01T01:00:00+01:00");
+
+    let later = time + Duration::new(86_400 * 365, 5);
+    assert_eq!(format!("{}", later), "2001-03-01T00:00:00.000000005Z");
+    assert!(later > time);
+    assert_eq!(
+        later.duration_since(&time),
+        Some(Duration::new(86_400 * 365, 5))
+    );
+    assert_eq!(time.duration_since(&later), None);
+    assert_eq!(later - Duration::new(86_400 * 365, 5), time);
+}
+
+fn reset_data() {
+    info!("Testing reset data");
+
+    let reason = [u16::from(b'O'), u16::from(b'K'), 0];
+    let reason =
+        uefi::CStr16::from_u16_with_nul(&reason).unwrap_or_else(|_| panic!("Invalid reset reason"));
+    let mut storage = [0; 32];
+
+    let data = ResetData::new(&mut storage, ResetType::Warm, reason, &[0xaa])
+        .expect("Failed to build reset data");
+    assert_eq!(data.reset_type(), ResetType::Warm);
+    assert_eq!(data.as_bytes(), &[b'O', 0, b'K', 0, 0, 0, 0xaa]);
+
+    let guid = uefi::Guid::from_values(
+        0x2b9c_4d61,
+        0x8a3f,
+        0x4e12,
+        0xb5c7,
+        [0x0d, 0x6e, 0x91, 0x3a, 0x58, 0xf4],
+    );
+    let data = ResetData::platform_specific(&mut storage, reason, &guid, &[])
+        .expect("Failed to build platform-specific reset data");
+    assert_eq!(data.reset_type(), ResetType::PlatformSpecific);
+    assert_eq!(data.as_bytes().len(), 6 + 16);
+    assert_eq!(&data.as_bytes()[6..], &guid.to_bytes());
+
+    let mut storage = [0; 4];
+    assert_eq!(
+        ResetData::new(&mut storage, ResetType::Cold, reason, &[]).err(),
+        Some(ResetDataError::InsufficientStorage(6))
+    );
+    assert_eq!(
+        ResetData::new(&mut storage, ResetType::PlatformSpecific, reason, &[]).err(),
+        Some(ResetDataError::PlatformSpecificNeedsGuid)
+    );
+}
+
+fn os_indications(rt: &RuntimeServices) {
+    info!("Testing OsIndications");
+
+    let supported = match rt.os_indications_supported() {
+        Ok(supported) => supported.unwrap(),
+        Err(err) if err.status() == Status::NOT_FOUND => {
+            warn!("OsIndications are not supported");
+            return;
+        }
+        Err(err) => panic!("Failed to read OsIndicationsSupported: {:?}", err),
+    };
+    info!("Supported OS indications: {:?}", supported);
+
+    // Save the variable, so that it can be restored for the next boot.
+    let saved = match rt.get_variable("OsIndications", &Variable::GLOBAL_VARIABLE_GUID) {
+        Ok(completion) => Some(completion.unwrap()),
+        Err(err) if err.status() == Status::NOT_FOUND => None,
+        Err(err) => panic!("Failed to read OsIndications: {:?}", err),
+    };
+
+    let indications = rt
+        .os_indications()
+        .expect_success("Failed to read OsIndications");
+    rt.set_os_indications(indications)
+        .expect_success("Failed to write OsIndications");
+    assert_eq!(
+        rt.os_indications()
+            .expect_success("Failed to read OsIndications"),
+        indications
+    );
+
+    let (data, attributes) = saved.unwrap_or((Vec::new(), VariableAttributes::empty()));
+    rt.set_variable(
+        "OsIndications",
+        &Variable::GLOBAL_VARIABLE_GUID,
+        attributes,
+        &data,
+    )
+    .expect_success("Failed to restore OsIndications");
+}
+
+fn variable_names(rt: &RuntimeServices) {
+    info!("Testing variable enumeration");
+
+    let keys = rt
+        .variable_keys()
+        .map(|key| key.expect_success("Failed to enumerate variables"))
+        .collect::<Vec<_>>();
+    info!("Found {} variables", keys.len());
+
+    // Enumerating the global variables into a fixed buffer must find the same names.
+    let mut buffer = [0; 256];
+    let mut names = rt
+        .variable_names(&mut buffer)
+        .vendor(Variable::GLOBAL_VARIABLE_GUID);
+    let mut global = keys
+        .iter()
+        .filter(|key| key.vendor() == Variable::GLOBAL_VARIABLE_GUID);
+    while let Some((name, vendor)) = names
+        .next_name()
+        .expect_success("Failed to enumerate global variables")
+    {
+        assert_eq!(vendor, Variable::GLOBAL_VARIABLE_GUID);
+        let key = global.next().expect("Unexpected global variable");
+        assert_eq!(name.to_u16_slice(), key.raw_name());
+        assert!(key.name().is_some());
+    }
+    assert!(global.next().is_none());
+
+    // The raw names are those of all the variables.
+    let mut names = rt.variable_names(&mut buffer);
+    let mut all = keys.iter();
+    while let Some((name, vendor)) = names
+        .next_raw_name()
+        .expect_success("Failed to enumerate variables")
+    {
+        let key = all.next().expect("Unexpected variable");
+        assert_eq!((name, vendor), (key.raw_name(), key.vendor()));
+    }
+    assert!(all.next().is_none());
+
+    // A buffer which cannot hold any name reports the required size, and
+    // the enumeration can continue into a larger buffer.
+    if !keys.is_empty() {
+        let mut small_buffer = [0; 1];
+        let mut names = rt.variable_names(&mut small_buffer);
+        let err = names
+            .next_raw_name()
+            .expect_error("An empty name buffer should be rejected");
+        assert_eq!(err.status(), Status::BUFFER_TOO_SMALL);
+        let len = err.data().unwrap();
+        assert!(len > 1);
+
+        let mut buffer = vec![0; len];
+        let mut names = names
+            .with_buffer(&mut buffer)
+            .unwrap_or_else(|_| panic!("Failed to switch to a larger buffer"));
+        let (name, vendor) = names
+            .next_raw_name()
+            .expect_success("Failed to enumerate variables")
+            .expect("The first variable is missing");
+        assert_eq!((name, vendor), (keys[0].raw_name(), keys[0].vendor()));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, UefiVariable)]
+#[repr(C)]
+#[variable(
+    vendor = "4c1d7f8e-2a95-4b36-9e0c-71d5a3f62b48",
+    name = "UefiRsTestConfig",
+    attributes = "BOOTSERVICE_ACCESS"
+)]
+struct TestConfig {
+    version: u32,
+    flags: u32,
+    counter: u64,
+}
+
+fn typed_variable(rt: &RuntimeServices) {
+    info!("Testing typed variables");
+
+    assert_eq!(TestConfig::NAME, "UefiRsTestConfig");
+    assert_eq!(
+        TestConfig::ATTRIBUTES,
+        VariableAttributes::BOOTSERVICE_ACCESS
+    );
+
+    let config = TestConfig {
+        version: 1,
+        flags: 0x8000_0001,
+        counter: 42,
+    };
+    rt.write(&config)
+        .expect_success("Failed to write typed variable");
+    let (data, _) = rt
+        .get_variable(TestConfig::NAME, &TestConfig::VENDOR)
+        .expect_success("Failed to read typed variable data");
+    assert_eq!(data.len(), 16);
+    assert_eq!(
+        rt.read::<TestConfig>()
+            .expect_success("Failed to read typed variable"),
+        config
+    );
+
+    // Data of the wrong size must not be decoded
+    rt.set_variable(
+        TestConfig::NAME,
+        &TestConfig::VENDOR,
+        TestConfig::ATTRIBUTES,
+        &[0; 4],
+    )
+    .expect_success("Failed to write typed variable data");
+    let err = rt
+        .read::<TestConfig>()
+        .expect_error("Truncated data should be rejected");
+    assert_eq!(err.status(), Status::COMPROMISED_DATA);
+
+    rt.set_variable(
+        TestConfig::NAME,
+        &TestConfig::VENDOR,
+        TestConfig::ATTRIBUTES,
+        &[],
+    )
+    .expect_success("Failed to delete typed variable");
+}
+
+/// Runtime services used by the virtual address change notification
+static RUNTIME_SERVICES: AtomicPtr<RuntimeServices> = AtomicPtr::new(ptr::null_mut());
+
+/// Whether the virtual address change notification converted its pointers
+static POINTERS_CONVERTED: AtomicBool = AtomicBool::new(false);
+
+/// Registers a notification which calls `convert_pointer` when the runtime
+/// services switch to virtual addressing.
+pub fn register_virtual_address_change(st: &SystemTable<Boot>) {
+    let rt = st.runtime_services() as *const RuntimeServices;
+    RUNTIME_SERVICES.store(rt as *mut _, Ordering::Relaxed);
+
+    let event = unsafe {
+        st.boot_services().create_event(
+            EventType::SIGNAL_VIRTUAL_ADDRESS_CHANGE,
+            Tpl::NOTIFY,
+            Some(convert_pointers),
+        )
+    }
+    .expect_success("Failed to create virtual address change event");
+    // The event must stay alive until `set_virtual_address_map` is called.
+    event.into_raw();
+}
+
+fn convert_pointers(_event: Event) {
+    let rt = RUNTIME_SERVICES.load(Ordering::Relaxed) as *const RuntimeServices;
+    let rt_ref = unsafe { &*rt };
+
+    let null = unsafe { rt_ref.convert_pointer(ptr::null::<u8>()) }
+        .expect_success("Failed to convert a null pointer");
+    // The runtime services table lives in runtime memory, which is mapped
+    // to the same addresses by `set_virtual_address_map` below.
+    let converted = unsafe { rt_ref.convert_pointer(rt) }
+        .expect_success("Failed to convert a pointer to runtime memory");
+    POINTERS_CONVERTED.store(null.is_null() && converted == rt, Ordering::Relaxed);
+}
+
+/// Switches the runtime services to an identity mapping, after boot
+/// services have been exited.
+pub fn set_virtual_address_map(
+    st: SystemTable<Runtime>,
+    map: &mut [MemoryDescriptor],
+) -> SystemTable<Runtime> {
+    // An empty map does not contain the system table, which is given back.
+    let err = unsafe { st.set_virtual_address_map(&mut []) }
+        .expect_error("The system table should not be found in an empty map");
+    assert_eq!(err.status(), Status::INVALID_PARAMETER);
+    let (_, st) = err.split();
+
+    // The memory is identity-mapped, so the new mapping is active right away.
+    for desc in map.iter_mut() {
+        desc.virt_start = desc.phys_start;
+    }
+    let st = unsafe { st.set_virtual_address_map(map) }
+        .expect_success("Failed to set the virtual address map");
+    assert!(
+        POINTERS_CONVERTED.load(Ordering::Relaxed),
+        "Pointers should have been converted to their virtual addresses"
+    );
+    st
+}